@@ -0,0 +1,198 @@
+// đọc các attribute #[json(...)]
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{
+    Attribute, Data, DeriveInput, Error, ExprPath, Fields, GenericParam, Generics, Ident, LitStr,
+    Result, Type,
+};
+
+// cách biểu diễn enum
+pub enum Tagging {
+    External,
+    Internal(String),
+    Untagged,
+}
+
+pub enum DefaultValue {
+    // Default::default()
+    Trait,
+    Function(ExprPath),
+}
+
+pub struct Field {
+    // tên field, hoặc index với tuple struct
+    pub member: TokenStream,
+    // tên biến khi destructure trong `match`: `__field0`, ... để không trùng
+    // với biến của code sinh ra
+    pub binding: Ident,
+    pub ty: Type,
+    pub key: String,
+    pub default: Option<DefaultValue>,
+    pub skip: bool,
+}
+
+pub enum Shape {
+    Named(Vec<Field>),
+    Tuple(Vec<Field>),
+    Unit,
+}
+
+pub struct Variant {
+    pub ident: Ident,
+    pub name: String,
+    pub shape: Shape,
+}
+
+fn json_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("json"))
+}
+
+pub fn tagging(input: &DeriveInput) -> Result<Tagging> {
+    let mut tagging = Tagging::External;
+    for attr in json_attrs(&input.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if !matches!(tagging, Tagging::External) {
+                return Err(meta.error("`tag` and `untagged` can not be combined"));
+            }
+            if meta.path.is_ident("tag") {
+                let tag: LitStr = meta.value()?.parse()?;
+                tagging = Tagging::Internal(tag.value());
+            } else if meta.path.is_ident("untagged") {
+                tagging = Tagging::Untagged;
+            } else {
+                return Err(meta.error("unknown json attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    if !matches!(tagging, Tagging::External) && !matches!(input.data, Data::Enum(_)) {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`tag` and `untagged` only apply to enums",
+        ));
+    }
+    Ok(tagging)
+}
+
+pub fn shape(fields: &Fields) -> Result<Shape> {
+    let (named, fields) = match fields {
+        Fields::Unit => return Ok(Shape::Unit),
+        Fields::Named(fields) => (true, &fields.named),
+        Fields::Unnamed(fields) => (false, &fields.unnamed),
+    };
+
+    let mut res = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let binding = format_ident!("__field{}", i);
+        let (member, mut key) = match &field.ident {
+            Some(ident) => (quote!(#ident), ident.unraw().to_string()),
+            None => {
+                let index = syn::Index::from(i);
+                (quote!(#index), i.to_string())
+            }
+        };
+        let (mut default, mut skip) = (None, false);
+
+        for attr in json_attrs(&field.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    key = name.value();
+                } else if meta.path.is_ident("default") {
+                    default = Some(match meta.value() {
+                        Ok(value) => DefaultValue::Function(value.parse::<LitStr>()?.parse()?),
+                        Err(_) => DefaultValue::Trait,
+                    });
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                } else {
+                    return Err(meta.error("unknown json attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        // field của tuple luôn có mặt trong array, nên không bỏ qua hay mặc định được
+        if !named && (skip || default.is_some()) {
+            return Err(Error::new_spanned(
+                field,
+                "`skip` and `default` are not supported on tuple fields",
+            ));
+        }
+
+        res.push(Field {
+            member,
+            binding,
+            ty: field.ty.clone(),
+            key,
+            default,
+            skip,
+        });
+    }
+
+    Ok(if named {
+        Shape::Named(res)
+    } else {
+        Shape::Tuple(res)
+    })
+}
+
+pub fn variants(input: &DeriveInput) -> Result<Vec<Variant>> {
+    let Data::Enum(data) = &input.data else {
+        unreachable!()
+    };
+    data.variants
+        .iter()
+        .map(|variant| {
+            let mut name = variant.ident.unraw().to_string();
+            for attr in json_attrs(&variant.attrs) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        name = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else {
+                        Err(meta.error("unknown json attribute"))
+                    }
+                })?;
+            }
+            Ok(Variant {
+                ident: variant.ident.clone(),
+                name,
+                shape: shape(&variant.fields)?,
+            })
+        })
+        .collect()
+}
+
+// internally tagged chỉ dùng được cho variant struct hoặc unit: nội dung của
+// newtype không chắc là object để đặt tag vào; field trùng key với tag sẽ ghi đè tag
+pub fn check_variant(tagging: &Tagging, variant: &Variant) -> Result<()> {
+    match (tagging, &variant.shape) {
+        (Tagging::Internal(_), Shape::Tuple(_)) => Err(Error::new_spanned(
+            &variant.ident,
+            "internally tagged enums can not have tuple or newtype variants",
+        )),
+        (Tagging::Internal(tag), Shape::Named(fields)) => {
+            match fields.iter().find(|f| !f.skip && f.key == *tag) {
+                Some(field) => Err(Error::new_spanned(
+                    &field.member,
+                    format!("`{}` is both the enum tag and a field key", tag),
+                )),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+// thêm bound `T: #bound` cho mọi type parameter
+pub fn with_bound(generics: &Generics, bound: TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds
+                .push(syn::parse2(bound.clone()).expect("valid trait bound"));
+        }
+    }
+    generics
+}
@@ -0,0 +1,207 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Ident, Result};
+
+use crate::attr::{self, check_variant, DefaultValue, Field, Shape, Tagging, Variant};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let tagging = attr::tagging(input)?;
+    let ident = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => shape_from_json(&attr::shape(&data.fields)?, quote!(Self)),
+        Data::Enum(_) => {
+            let variants = attr::variants(input)?;
+            for variant in &variants {
+                check_variant(&tagging, variant)?;
+            }
+            match &tagging {
+                Tagging::External => external(&variants),
+                Tagging::Internal(tag) => internal(tag, &variants),
+                Tagging::Untagged => untagged(ident, &variants),
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(ident, "unions are not supported"));
+        }
+    };
+
+    let generics = attr::with_bound(&input.generics, quote!(::json_parser::FromJson));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::json_parser::FromJson for #ident #ty_generics #where_clause {
+            fn from_json(
+                value: &::json_parser::Value,
+            ) -> ::std::result::Result<Self, ::json_parser::FromJsonError> {
+                #body
+            }
+        }
+    })
+}
+
+// thân hàm đọc `value` thành `ctor`, có thể `return Err(..)`
+fn shape_from_json(shape: &Shape, ctor: TokenStream) -> TokenStream {
+    match shape {
+        Shape::Named(fields) => {
+            let inits = fields.iter().map(field_from_json);
+            quote! {
+                let object = match value {
+                    ::json_parser::Value::Object(object) => object,
+                    other => return ::std::result::Result::Err(::json_parser::FromJsonError::expected("object", other)),
+                };
+                ::std::result::Result::Ok(#ctor { #(#inits),* })
+            }
+        }
+        Shape::Tuple(fields) if fields.len() == 1 => {
+            let ty = &fields[0].ty;
+            quote!(::std::result::Result::Ok(#ctor(<#ty as ::json_parser::FromJson>::from_json(value)?)))
+        }
+        Shape::Tuple(fields) => {
+            let len = fields.len();
+            let expected = format!("array of length {}", len);
+            let items = fields.iter().enumerate().map(|(i, field)| {
+                let ty = &field.ty;
+                quote! {
+                    <#ty as ::json_parser::FromJson>::from_json(&items[#i]).map_err(|e| e.at(#i))?
+                }
+            });
+            quote! {
+                let items = match value {
+                    ::json_parser::Value::Array(items) if items.len() == #len => items,
+                    other => return ::std::result::Result::Err(::json_parser::FromJsonError::expected(#expected, other)),
+                };
+                ::std::result::Result::Ok(#ctor(#(#items),*))
+            }
+        }
+        Shape::Unit => quote! {
+            match value {
+                ::json_parser::Value::Null => ::std::result::Result::Ok(#ctor),
+                other => ::std::result::Result::Err(::json_parser::FromJsonError::expected("null", other)),
+            }
+        },
+    }
+}
+
+fn field_from_json(field: &Field) -> TokenStream {
+    let (member, ty, key) = (&field.member, &field.ty, &field.key);
+    if field.skip {
+        return quote!(#member: ::std::default::Default::default());
+    }
+    let missing = match &field.default {
+        Some(DefaultValue::Trait) => quote!(::std::default::Default::default()),
+        Some(DefaultValue::Function(path)) => quote!(#path()),
+        None => quote! {
+            match <#ty as ::json_parser::FromJson>::from_missing() {
+                ::std::option::Option::Some(value) => value,
+                ::std::option::Option::None => return ::std::result::Result::Err(::json_parser::FromJsonError::missing_field(#key)),
+            }
+        },
+    };
+    quote! {
+        #member: match object.get(#key) {
+            ::std::option::Option::Some(value) => {
+                <#ty as ::json_parser::FromJson>::from_json(value).map_err(|e| e.at(#key))?
+            }
+            ::std::option::Option::None => #missing,
+        }
+    }
+}
+
+// đọc 1 variant mà không thoát khỏi `from_json`
+fn variant_closure(variant: &Variant) -> TokenStream {
+    let ident = &variant.ident;
+    let body = shape_from_json(&variant.shape, quote!(Self::#ident));
+    quote! {
+        (|value: &::json_parser::Value| -> ::std::result::Result<Self, ::json_parser::FromJsonError> {
+            #body
+        })
+    }
+}
+
+fn unknown_variant(variants: &[Variant]) -> TokenStream {
+    let names = variants.iter().map(|v| &v.name);
+    quote!(::json_parser::FromJsonError::unknown_variant(other, &[#(#names),*]))
+}
+
+// "Unit" hoặc {"Variant": content}
+fn external(variants: &[Variant]) -> TokenStream {
+    let units = variants
+        .iter()
+        .filter(|v| matches!(v.shape, Shape::Unit))
+        .map(|v| {
+            let (ident, name) = (&v.ident, &v.name);
+            quote!(#name => ::std::result::Result::Ok(Self::#ident),)
+        });
+    let arms = variants.iter().map(|v| {
+        let (name, closure) = (&v.name, variant_closure(v));
+        quote!(#name => #closure(value).map_err(|e| e.at(#name)),)
+    });
+    let unknown = unknown_variant(variants);
+    quote! {
+        match value {
+            ::json_parser::Value::String(name) => match name.as_str() {
+                #(#units)*
+                other => ::std::result::Result::Err(#unknown),
+            },
+            ::json_parser::Value::Object(object) if object.len() == 1 => {
+                let (name, value) = object.iter().next().unwrap();
+                match name.as_str() {
+                    #(#arms)*
+                    other => ::std::result::Result::Err(#unknown),
+                }
+            }
+            other => ::std::result::Result::Err(::json_parser::FromJsonError::expected(
+                "string or object with a single key",
+                other,
+            )),
+        }
+    }
+}
+
+// {"tag": "Variant", ...fields}
+fn internal(tag: &str, variants: &[Variant]) -> TokenStream {
+    let arms = variants.iter().map(|v| {
+        let (ident, name) = (&v.ident, &v.name);
+        match v.shape {
+            Shape::Unit => quote!(#name => ::std::result::Result::Ok(Self::#ident),),
+            _ => {
+                let closure = variant_closure(v);
+                quote!(#name => #closure(value),)
+            }
+        }
+    });
+    let unknown = unknown_variant(variants);
+    quote! {
+        let object = match value {
+            ::json_parser::Value::Object(object) => object,
+            other => return ::std::result::Result::Err(::json_parser::FromJsonError::expected("object", other)),
+        };
+        let name = match object.get(#tag) {
+            ::std::option::Option::Some(::json_parser::Value::String(name)) => name,
+            ::std::option::Option::Some(other) => {
+                return ::std::result::Result::Err(::json_parser::FromJsonError::expected("string", other).at(#tag));
+            }
+            ::std::option::Option::None => return ::std::result::Result::Err(::json_parser::FromJsonError::missing_field(#tag)),
+        };
+        match name.as_str() {
+            #(#arms)*
+            other => ::std::result::Result::Err(#unknown.at(#tag)),
+        }
+    }
+}
+
+// thử lần lượt từng variant, lấy cái đầu tiên đọc được
+fn untagged(ident: &Ident, variants: &[Variant]) -> TokenStream {
+    let closures = variants.iter().map(variant_closure);
+    let message = format!(
+        "data did not match any variant of untagged enum `{}`",
+        ident
+    );
+    quote! {
+        #(
+            if let ::std::result::Result::Ok(res) = #closures(value) {
+                return ::std::result::Result::Ok(res);
+            }
+        )*
+        ::std::result::Result::Err(::json_parser::FromJsonError::new(#message))
+    }
+}
@@ -0,0 +1,30 @@
+// #[derive(ToJson, FromJson)] cho json-parser
+//
+// field:   #[json(rename = "name")], #[json(default)], #[json(default = "path::to_fn")], #[json(skip)]
+//          (default / skip chỉ dùng cho field có tên)
+// variant: #[json(rename = "name")]
+// enum:    mặc định là externally tagged {"Variant": ...},
+//          #[json(tag = "type")] cho internally tagged (chỉ variant struct / unit,
+//          không field nào được trùng key với tag), #[json(untagged)]
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, Error};
+
+mod attr;
+mod from_json;
+mod to_json;
+
+#[proc_macro_derive(ToJson, attributes(json))]
+pub fn derive_to_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    to_json::expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(FromJson, attributes(json))]
+pub fn derive_from_json(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_json::expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
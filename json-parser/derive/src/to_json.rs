@@ -0,0 +1,150 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Result};
+
+use crate::attr::{self, check_variant, Field, Shape, Tagging};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let tagging = attr::tagging(input)?;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let shape = attr::shape(&data.fields)?;
+            let access = |field: &Field| {
+                let member = &field.member;
+                quote!(&self.#member)
+            };
+            shape_to_json(&shape, access)
+        }
+        Data::Enum(_) => {
+            let arms = attr::variants(input)?
+                .into_iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let pattern = match &variant.shape {
+                        Shape::Named(fields) => {
+                            let fields = fields.iter().filter(|f| !f.skip);
+                            let members = fields.clone().map(|f| &f.member);
+                            let bindings = fields.map(|f| &f.binding);
+                            quote!(Self::#ident { #(#members: #bindings,)* .. })
+                        }
+                        Shape::Tuple(fields) => {
+                            let bindings = fields.iter().map(|f| &f.binding);
+                            quote!(Self::#ident(#(#bindings),*))
+                        }
+                        Shape::Unit => quote!(Self::#ident),
+                    };
+                    let access = |field: &Field| {
+                        let binding = &field.binding;
+                        quote!(#binding)
+                    };
+                    check_variant(&tagging, &variant)?;
+                    let value = tag(&tagging, &variant.name, &variant.shape, access);
+                    Ok(quote!(#pattern => #value,))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {
+                #[allow(unused_variables)]
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(&input.ident, "unions are not supported"));
+        }
+    };
+
+    let ident = &input.ident;
+    let generics = attr::with_bound(&input.generics, quote!(::json_parser::ToJson));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::json_parser::ToJson for #ident #ty_generics #where_clause {
+            fn to_json(&self) -> ::json_parser::Value {
+                #body
+            }
+        }
+    })
+}
+
+// struct -> object, tuple 1 field -> chính field đó, tuple -> array, unit -> null
+fn shape_to_json(shape: &Shape, access: impl Fn(&Field) -> TokenStream) -> TokenStream {
+    match shape {
+        Shape::Named(fields) => {
+            let object = object(None, fields, access);
+            quote!(::json_parser::Value::Object(#object))
+        }
+        Shape::Tuple(fields) if fields.len() == 1 => {
+            let value = access(&fields[0]);
+            quote!(::json_parser::ToJson::to_json(#value))
+        }
+        Shape::Tuple(fields) => {
+            let values = fields.iter().map(access);
+            quote! {
+                ::json_parser::Value::Array(::std::vec![
+                    #(::json_parser::ToJson::to_json(#values)),*
+                ])
+            }
+        }
+        Shape::Unit => quote!(::json_parser::Value::Null),
+    }
+}
+
+// `tag` = (key, tên variant) được đặt lên đầu, cùng cấp với các field
+fn object(
+    tag: Option<(&str, &str)>,
+    fields: &[Field],
+    access: impl Fn(&Field) -> TokenStream,
+) -> TokenStream {
+    let tag = tag.map(|(key, name)| {
+        quote! {
+            __object.insert(
+                ::std::string::String::from(#key),
+                ::json_parser::Value::String(::std::string::String::from(#name)),
+            );
+        }
+    });
+    let inserts = fields.iter().filter(|f| !f.skip).map(|field| {
+        let key = &field.key;
+        let value = access(field);
+        quote! {
+            __object.insert(::std::string::String::from(#key), ::json_parser::ToJson::to_json(#value));
+        }
+    });
+    quote! {{
+        let mut __object = ::json_parser::Map::new();
+        #tag
+        #(#inserts)*
+        __object
+    }}
+}
+
+fn tag(
+    tagging: &Tagging,
+    name: &str,
+    shape: &Shape,
+    access: impl Fn(&Field) -> TokenStream,
+) -> TokenStream {
+    match (tagging, shape) {
+        (Tagging::Untagged, _) => shape_to_json(shape, access),
+        (Tagging::External, Shape::Unit) => {
+            quote!(::json_parser::Value::String(::std::string::String::from(#name)))
+        }
+        (Tagging::External, _) => {
+            let content = shape_to_json(shape, access);
+            quote! {{
+                let mut __object = ::json_parser::Map::new();
+                __object.insert(::std::string::String::from(#name), #content);
+                ::json_parser::Value::Object(__object)
+            }}
+        }
+        // check_variant đã loại variant tuple / newtype
+        (Tagging::Internal(tag), Shape::Named(fields)) => {
+            let object = object(Some((tag, name)), fields, access);
+            quote!(::json_parser::Value::Object(#object))
+        }
+        (Tagging::Internal(tag), _) => {
+            let object = object(Some((tag, name)), &[], access);
+            quote!(::json_parser::Value::Object(#object))
+        }
+    }
+}
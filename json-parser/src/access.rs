@@ -0,0 +1,396 @@
+// truy cập Value không cần `match`: value["presenter"]["name"].as_str()
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::ops;
+
+use crate::{Map, Number, Value};
+
+// value["missing"] trả về reference tới đây thay vì panic
+static NULL: Value = Value::Null;
+
+// kiểu dùng được để index vào Value: key của object hoặc index của array
+pub trait ValueIndex {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value>;
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value>;
+}
+
+impl ValueIndex for usize {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match value {
+            Value::Array(items) => items.get(*self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        match value {
+            Value::Array(items) => items.get_mut(*self),
+            _ => None,
+        }
+    }
+}
+
+impl ValueIndex for str {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match value {
+            Value::Object(object) => object.get(self),
+            _ => None,
+        }
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        match value {
+            Value::Object(object) => object.get_mut(self),
+            _ => None,
+        }
+    }
+}
+
+impl ValueIndex for String {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        self.as_str().index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        self.as_str().index_into_mut(value)
+    }
+}
+
+impl<T: ValueIndex + ?Sized> ValueIndex for &T {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        (**self).index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        (**self).index_into_mut(value)
+    }
+}
+
+impl<I: ValueIndex> ops::Index<I> for Value {
+    type Output = Value;
+
+    fn index(&self, index: I) -> &Value {
+        self.get(index).unwrap_or(&NULL)
+    }
+}
+
+impl Value {
+    pub fn get<I: ValueIndex>(&self, index: I) -> Option<&Value> {
+        index.index_into(self)
+    }
+
+    pub fn get_mut<I: ValueIndex>(&mut self, index: I) -> Option<&mut Value> {
+        index.index_into_mut(self)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::True => Some(true),
+            Value::False => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<Number> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map<String, Value>> {
+        match self {
+            Value::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut Map<String, Value>> {
+        match self {
+            Value::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Value::True | Value::False)
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, Value::Number(_))
+    }
+
+    pub fn is_i64(&self) -> bool {
+        matches!(self, Value::Number(n) if n.is_i64())
+    }
+
+    pub fn is_u64(&self) -> bool {
+        matches!(self, Value::Number(n) if n.is_u64())
+    }
+
+    pub fn is_f64(&self) -> bool {
+        matches!(self, Value::Number(n) if n.is_f64())
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::String(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
+    }
+
+    pub fn is_object(&self) -> bool {
+        matches!(self, Value::Object(_))
+    }
+
+    // lấy value ra, để lại null
+    pub fn take(&mut self) -> Value {
+        std::mem::replace(self, Value::Null)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        if b {
+            Value::True
+        } else {
+            Value::False
+        }
+    }
+}
+
+macro_rules! from_signed {
+    ($($t:ty)*) => {$(
+        impl From<$t> for Value {
+            fn from(n: $t) -> Self {
+                Value::Number(Number::Int(n as i64))
+            }
+        }
+    )*};
+}
+from_signed!(i8 i16 i32 i64 isize);
+
+// giống parser: số vừa i64 thì dùng Int
+macro_rules! from_unsigned {
+    ($($t:ty)*) => {$(
+        impl From<$t> for Value {
+            fn from(n: $t) -> Self {
+                let n = n as u64;
+                Value::Number(match i64::try_from(n) {
+                    Ok(n) => Number::Int(n),
+                    Err(_) => Number::UInt(n),
+                })
+            }
+        }
+    )*};
+}
+from_unsigned!(u8 u16 u32 u64 usize);
+
+// đi qua cách viết ngắn nhất của f32, để 0.1f32 thành 0.1 chứ không phải 0.10000000149011612
+impl From<f32> for Value {
+    fn from(n: f32) -> Self {
+        Value::Number(Number::Float(n.to_string().parse().unwrap_or(n as f64)))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::Number(Number::Float(n))
+    }
+}
+
+impl From<Number> for Value {
+    fn from(n: Number) -> Self {
+        Value::Number(n)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Value {
+    fn from(s: Cow<'a, str>) -> Self {
+        Value::String(s.into_owned())
+    }
+}
+
+// None -> null
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Self {
+        Value::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Clone + Into<Value>> From<&[T]> for Value {
+    fn from(items: &[T]) -> Self {
+        Value::Array(items.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl From<Map<String, Value>> for Value {
+    fn from(object: Map<String, Value>) -> Self {
+        Value::Object(object)
+    }
+}
+
+// HashMap không có thứ tự nên key được sắp xếp, để output ổn định
+impl<K: Into<String> + Ord, V: Into<Value>> From<HashMap<K, V>> for Value {
+    fn from(map: HashMap<K, V>) -> Self {
+        let mut entries: Vec<(K, V)> = map.into_iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        entries.into_iter().collect()
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> From<BTreeMap<K, V>> for Value {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> FromIterator<(K, V)> for Value {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Value::Object(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_index() {
+        let src = std::fs::read_to_string("tests/5.json").expect("Can not found test file");
+        let value = parse(&src).unwrap();
+
+        assert_eq!(value["presenter"]["name"].as_str(), Some("Dung"));
+        assert_eq!(value["presenter"]["age"].as_i64(), Some(27));
+        assert_eq!(value["organizers"][1].as_str(), Some("techfest"));
+        assert_eq!(value["live"].as_bool(), Some(true));
+        assert_eq!(value[&"year".to_string()].as_u64(), Some(2023));
+
+        // thiếu key / sai kiểu thì ra null chứ không panic
+        assert!(value["missing"]["deeper"].is_null());
+        assert!(value["organizers"][9].is_null());
+        assert!(value["title"][0].is_null());
+        assert!(value[0].is_null());
+    }
+
+    #[test]
+    fn test_get_mut_and_take() {
+        let mut value = parse(r#"{"a": [1, {"b": "x"}]}"#).unwrap();
+        assert_eq!(value.get("a").and_then(|a| a.get(0)), Some(&Value::from(1)));
+        assert_eq!(value.get("b"), None);
+
+        *value.get_mut("a").unwrap().get_mut(0).unwrap() = Value::from("one");
+        assert!(value["a"][1].as_object().is_some());
+        let b = value
+            .get_mut("a")
+            .and_then(|a| a.get_mut(1))
+            .unwrap()
+            .take();
+        assert_eq!(b.to_string(), r#"{"b":"x"}"#);
+        assert_eq!(value.to_string(), r#"{"a":["one",null]}"#);
+
+        assert!(value.as_array_mut().is_none());
+        value
+            .as_object_mut()
+            .unwrap()
+            .insert("c".to_string(), Value::from(true));
+        assert_eq!(value.to_string(), r#"{"a":["one",null],"c":true}"#);
+    }
+
+    #[test]
+    fn test_predicates() {
+        let value =
+            parse(r#"[null, true, 1, -1, 18446744073709551615, 1.5, "s", [], {}]"#).unwrap();
+        let items = value.as_array().unwrap();
+        assert!(items[0].is_null());
+        assert!(items[1].is_bool());
+        assert!(items[2].is_i64() && items[2].is_number());
+        assert!(!items[3].is_u64());
+        assert!(items[4].is_u64() && !items[4].is_i64());
+        assert!(items[5].is_f64());
+        assert!(items[6].is_string());
+        assert!(items[7].is_array());
+        assert!(items[8].is_object());
+        assert_eq!(items[5].as_number(), Some(Number::Float(1.5)));
+        assert!(items.iter().all(|v| v.as_bool().is_none() || v.is_bool()));
+    }
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Value::from(true), Value::True);
+        assert_eq!(Value::from(7u8), Value::Number(Number::Int(7)));
+        assert_eq!(Value::from(u64::MAX), Value::Number(Number::UInt(u64::MAX)));
+        assert_eq!(Value::from(-3i32), Value::Number(Number::Int(-3)));
+        assert_eq!(Value::from(0.5f32), Value::Number(Number::Float(0.5)));
+        assert_eq!(Value::from(None::<i32>), Value::Null);
+        assert_eq!(
+            Value::from(Cow::Borrowed("c")),
+            Value::from("c".to_string())
+        );
+        assert_eq!(Value::from(vec![1, 2]).to_string(), "[1,2]");
+        assert_eq!(Value::from(&["a", "b"][..]).to_string(), r#"["a","b"]"#);
+
+        let hash: HashMap<&str, Value> = [
+            ("b", Value::from(2)),
+            ("a", Value::from(vec![Some(1), None])),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(Value::from(hash).to_string(), r#"{"a":[1,null],"b":2}"#);
+
+        let tree: BTreeMap<String, f64> = [("x".to_string(), 1.5)].into_iter().collect();
+        assert_eq!(Value::from(tree).to_string(), r#"{"x":1.5}"#);
+
+        let object: Value = vec![("k", "v")].into_iter().collect();
+        assert_eq!(object["k"], Value::from("v"));
+    }
+}
@@ -0,0 +1,120 @@
+// Value mượn dữ liệu từ input: key và string không có escape trỏ thẳng vào
+// input thay vì copy ra String mới, chỉ string có escape mới phải cấp phát
+use std::borrow::Cow;
+
+use crate::{parse_document, Build, Event, Events, Map, Number, ParseError, ParseOptions};
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Value<'a> {
+    Null,
+    Number(Number),
+    True,
+    False,
+    String(Cow<'a, str>),
+    Array(Vec<Value<'a>>),
+    Object(Map<Cow<'a, str>, Value<'a>>),
+}
+
+impl Value<'_> {
+    // copy mọi phần còn mượn để Value sống lâu hơn input
+    pub fn into_owned(self) -> crate::Value {
+        match self {
+            Value::Null => crate::Value::Null,
+            Value::Number(n) => crate::Value::Number(n),
+            Value::True => crate::Value::True,
+            Value::False => crate::Value::False,
+            Value::String(s) => crate::Value::String(s.into_owned()),
+            Value::Array(items) => {
+                crate::Value::Array(items.into_iter().map(Value::into_owned).collect())
+            }
+            Value::Object(object) => crate::Value::Object(
+                object
+                    .into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<'a> Build<'a> for Value<'a> {
+    type Key = Cow<'a, str>;
+
+    fn key(key: Cow<'a, str>) -> Cow<'a, str> {
+        key
+    }
+
+    fn scalar(event: Event<'a>) -> Value<'a> {
+        match event {
+            Event::String(s) => Value::String(s),
+            Event::Number(n) => Value::Number(n),
+            Event::Bool(true) => Value::True,
+            Event::Bool(false) => Value::False,
+            Event::Null => Value::Null,
+            _ => unreachable!("not a scalar event"),
+        }
+    }
+
+    fn array(items: Vec<Value<'a>>) -> Value<'a> {
+        Value::Array(items)
+    }
+
+    fn object(object: Map<Cow<'a, str>, Value<'a>>) -> Value<'a> {
+        Value::Object(object)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Value<'_>, ParseError> {
+    parse_with(input, &ParseOptions::default())
+}
+
+pub fn parse_with<'a>(input: &'a str, options: &ParseOptions) -> Result<Value<'a>, ParseError> {
+    parse_document(&mut Events::with_options(input, options.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_borrowed_strings() {
+        let src = r#"{"plain": "hello world", "esc\"aped": ["line\nbreak", "x"]}"#;
+        let value = parse(src).unwrap();
+
+        let Value::Object(object) = &value else {
+            panic!("expected object");
+        };
+        let keys: Vec<_> = object.keys().collect();
+        assert!(matches!(keys[0], Cow::Borrowed("plain")));
+        assert!(matches!(keys[1], Cow::Owned(_)));
+        assert_eq!(keys[1], "esc\"aped");
+
+        match object.get("plain") {
+            Some(Value::String(s)) => assert!(matches!(s, Cow::Borrowed("hello world"))),
+            other => panic!("unexpected {:?}", other),
+        }
+        match object.get("esc\"aped") {
+            Some(Value::Array(items)) => {
+                assert_eq!(
+                    items[0],
+                    Value::String(Cow::Owned("line\nbreak".to_string()))
+                );
+                assert!(matches!(&items[0], Value::String(Cow::Owned(_))));
+                assert!(matches!(&items[1], Value::String(Cow::Borrowed("x"))));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert_eq!(value.into_owned(), crate::parse(src).unwrap());
+    }
+
+    #[test]
+    fn test_into_owned_fixtures() {
+        for i in 1..=5 {
+            let src = std::fs::read_to_string(format!("tests/{}.json", i))
+                .expect("Can not found test file");
+            let owned = parse(&src).unwrap().into_owned();
+            assert_eq!(Ok(owned), crate::parse(&src));
+        }
+    }
+}
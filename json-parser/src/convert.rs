@@ -0,0 +1,641 @@
+// chuyển kiểu Rust sang Value và ngược lại; `#[derive(ToJson, FromJson)]`
+// nằm ở crate json-parser-derive (feature `derive`)
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+
+use crate::pointer::to_pointer;
+use crate::schema::type_name;
+use crate::{Map, Number, Value};
+
+pub trait ToJson {
+    fn to_json(&self) -> Value;
+}
+
+pub trait FromJson: Sized {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError>;
+
+    // giá trị khi field không có trong object, mặc định là lỗi
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromJsonError {
+    // từ ngoài vào trong
+    path: Vec<String>,
+    message: String,
+}
+
+impl FromJsonError {
+    pub fn new(message: impl Into<String>) -> Self {
+        FromJsonError {
+            path: vec![],
+            message: message.into(),
+        }
+    }
+
+    pub fn expected(expected: &str, found: &Value) -> Self {
+        Self::new(format!("expected {}, found {}", expected, type_name(found)))
+    }
+
+    pub fn missing_field(name: &str) -> Self {
+        Self::new(format!("missing field `{}`", name))
+    }
+
+    pub fn unknown_variant(name: &str, variants: &[&str]) -> Self {
+        let variants: Vec<String> = variants.iter().map(|v| format!("`{}`", v)).collect();
+        Self::new(format!(
+            "unknown variant `{}`, expected one of {}",
+            name,
+            variants.join(", ")
+        ))
+    }
+
+    // lỗi xảy ra bên trong field / phần tử `token`
+    pub fn at(mut self, token: impl fmt::Display) -> Self {
+        self.path.insert(0, token.to_string());
+        self
+    }
+
+    // JSON Pointer tới value bị lỗi
+    pub fn path(&self) -> String {
+        to_pointer(&self.path)
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for FromJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{} at `{}`", self.message, self.path())
+        }
+    }
+}
+
+impl Error for FromJsonError {}
+
+impl ToJson for Value {
+    fn to_json(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl FromJson for Value {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        Ok(value.clone())
+    }
+}
+
+impl ToJson for () {
+    fn to_json(&self) -> Value {
+        Value::Null
+    }
+}
+
+impl FromJson for () {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        match value {
+            Value::Null => Ok(()),
+            other => Err(FromJsonError::expected("null", other)),
+        }
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        value
+            .as_bool()
+            .ok_or_else(|| FromJsonError::expected("boolean", value))
+    }
+}
+
+macro_rules! integer {
+    ($($t:ty)*) => {$(
+        impl ToJson for $t {
+            fn to_json(&self) -> Value {
+                Value::from(*self)
+            }
+        }
+
+        impl FromJson for $t {
+            fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+                let n = match value {
+                    Value::Number(Number::Int(n)) => <$t>::try_from(*n).ok(),
+                    Value::Number(Number::UInt(n)) => <$t>::try_from(*n).ok(),
+                    _ => return Err(FromJsonError::expected("integer", value)),
+                };
+                n.ok_or_else(|| {
+                    FromJsonError::new(format!("{} is out of range for {}", value, stringify!($t)))
+                })
+            }
+        }
+    )*};
+}
+integer!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+impl ToJson for f64 {
+    fn to_json(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        match value {
+            Value::Number(n) => n.as_f64().ok_or_else(|| {
+                FromJsonError::new(format!("{} can not be represented exactly as f64", value))
+            }),
+            other => Err(FromJsonError::expected("number", other)),
+        }
+    }
+}
+
+impl ToJson for f32 {
+    fn to_json(&self) -> Value {
+        Value::from(*self)
+    }
+}
+
+impl FromJson for f32 {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        f64::from_json(value).map(|n| n as f32)
+    }
+}
+
+impl ToJson for str {
+    fn to_json(&self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        value
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| FromJsonError::expected("string", value))
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> Value {
+        (**self).to_json()
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for Box<T> {
+    fn to_json(&self) -> Value {
+        (**self).to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Box<T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        T::from_json(value).map(Box::new)
+    }
+}
+
+// None <-> null, field kiểu Option có thể không có trong object
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> Value {
+        self.as_ref().map_or(Value::Null, ToJson::to_json)
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_json(value).map(Some),
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> Value {
+        Value::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> Value {
+        self.as_slice().to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        let Value::Array(items) = value else {
+            return Err(FromJsonError::expected("array", value));
+        };
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| T::from_json(item).map_err(|e| e.at(i)))
+            .collect()
+    }
+}
+
+fn object_from_json<T: FromJson, C: FromIterator<(String, T)>>(
+    value: &Value,
+) -> Result<C, FromJsonError> {
+    let Value::Object(object) = value else {
+        return Err(FromJsonError::expected("object", value));
+    };
+    object
+        .iter()
+        .map(|(k, v)| Ok((k.clone(), T::from_json(v).map_err(|e| e.at(k))?)))
+        .collect()
+}
+
+impl<T: ToJson> ToJson for Map<String, T> {
+    fn to_json(&self) -> Value {
+        Value::Object(self.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for Map<String, T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        object_from_json(value)
+    }
+}
+
+// sắp xếp key như `From<HashMap>` để output ổn định
+impl<T: ToJson> ToJson for HashMap<String, T> {
+    fn to_json(&self) -> Value {
+        let mut entries: Vec<(&String, &T)> = self.iter().collect();
+        entries.sort_by_key(|(k, _)| *k);
+        Value::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.clone(), v.to_json()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        object_from_json(value)
+    }
+}
+
+impl<T: ToJson> ToJson for BTreeMap<String, T> {
+    fn to_json(&self) -> Value {
+        Value::Object(self.iter().map(|(k, v)| (k.clone(), v.to_json())).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for BTreeMap<String, T> {
+    fn from_json(value: &Value) -> Result<Self, FromJsonError> {
+        object_from_json(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use json_parser_derive::{FromJson, ToJson};
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct Presenter {
+        name: String,
+        age: u8,
+        #[json(rename = "occupation")]
+        job: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct Event {
+        title: String,
+        year: u16,
+        live: bool,
+        organizers: Vec<String>,
+        presenter: Presenter,
+        #[json(default)]
+        tags: Vec<String>,
+        #[json(default = "default_room")]
+        room: String,
+        #[json(skip)]
+        cached: u32,
+    }
+
+    fn default_room() -> String {
+        "main".to_string()
+    }
+
+    #[test]
+    fn test_floats() {
+        assert_eq!(f64::from_json(&Value::from(1.5)), Ok(1.5));
+        assert_eq!(f64::from_json(&Value::from(-3)), Ok(-3.0));
+        assert_eq!(
+            f64::from_json(&Value::from(9007199254740992u64)),
+            Ok(9007199254740992.0)
+        );
+        // giống Number::as_f64: số nguyên không biểu diễn chính xác được thì báo lỗi
+        for n in [
+            Value::from(9007199254740993u64),
+            Value::from(-9007199254740993i64),
+            Value::Number(Number::UInt(u64::MAX)),
+        ] {
+            assert_eq!(f64::from_json(&n).ok(), n.as_f64());
+            let err = f64::from_json(&n).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("{} can not be represented exactly as f64", n)
+            );
+            assert!(f32::from_json(&n).is_err());
+        }
+        let err = f64::from_json(&Value::from("1")).unwrap_err();
+        assert_eq!(err.to_string(), "expected number, found string");
+    }
+
+    #[test]
+    fn test_derive_struct() {
+        let src = std::fs::read_to_string("tests/5.json").expect("Can not found test file");
+        let event = Event::from_json(&parse(&src).unwrap()).unwrap();
+        assert_eq!(
+            event,
+            Event {
+                title: "Rust".to_string(),
+                year: 2023,
+                live: true,
+                organizers: vec!["vbi".to_string(), "techfest".to_string()],
+                presenter: Presenter {
+                    name: "Dung".to_string(),
+                    age: 27,
+                    job: Some("Engineer".to_string()),
+                },
+                tags: vec![],
+                room: "main".to_string(),
+                cached: 0,
+            }
+        );
+
+        let value = event.to_json();
+        assert_eq!(value["presenter"]["occupation"].as_str(), Some("Engineer"));
+        assert!(value.get("cached").is_none());
+        assert_eq!(Event::from_json(&value), Ok(event));
+
+        let presenter = Presenter::from_json(&parse(r#"{"name": "An", "age": 30}"#).unwrap());
+        assert_eq!(presenter.unwrap().job, None);
+    }
+
+    #[test]
+    fn test_derive_errors() {
+        let err = Presenter::from_json(&parse(r#"{"name": "An"}"#).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `age`");
+
+        let err = Event::from_json(
+            &parse(
+                r#"{"title": "x", "year": 2023, "live": true, "organizers": ["a", 1],
+                    "presenter": {"name": "An", "age": 1}}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected string, found integer at `/organizers/1`"
+        );
+
+        let err =
+            Presenter::from_json(&parse(r#"{"name": "An", "age": 300}"#).unwrap()).unwrap_err();
+        assert_eq!(err.path(), "/age");
+        assert_eq!(err.message(), "300 is out of range for u8");
+
+        let err = Presenter::from_json(&parse("[]").unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "expected object, found array");
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct Point(i32, i32);
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct Meters(f64);
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct Marker;
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    struct Wrapper<T> {
+        items: Vec<T>,
+    }
+
+    #[test]
+    fn test_derive_tuple_and_generic() {
+        assert_eq!(Point(1, -2).to_json().to_string(), "[1,-2]");
+        assert_eq!(Point::from_json(&parse("[3, 4]").unwrap()), Ok(Point(3, 4)));
+        assert!(Point::from_json(&parse("[3]").unwrap()).is_err());
+        assert_eq!(Meters(1.5).to_json().to_string(), "1.5");
+        assert_eq!(Meters::from_json(&parse("2").unwrap()), Ok(Meters(2.0)));
+        assert_eq!(Marker.to_json(), Value::Null);
+        assert_eq!(Marker::from_json(&Value::Null), Ok(Marker));
+
+        let wrapper = Wrapper {
+            items: vec![Point(0, 1)],
+        };
+        assert_eq!(wrapper.to_json().to_string(), r#"{"items":[[0,1]]}"#);
+        assert_eq!(Wrapper::from_json(&wrapper.to_json()), Ok(wrapper));
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    enum Shape {
+        Empty,
+        #[json(rename = "circle")]
+        Circle {
+            radius: f64,
+        },
+        Square(Meters),
+        Line(Point, Point),
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    #[json(tag = "kind")]
+    enum Message {
+        Ping,
+        Text { body: String, to: Option<String> },
+        Join { name: String, room: u32 },
+    }
+
+    // field trùng tên với biến trong code sinh ra
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    enum Shadow {
+        Field { object: i32, value: i32 },
+    }
+
+    // tên trùng với prelude (Ok, None, Vec, ...) không được làm hỏng code sinh ra
+    mod prelude {
+        #![allow(dead_code)]
+        use json_parser_derive::{FromJson, ToJson};
+
+        struct Ok;
+        struct Err;
+        struct Some;
+        struct None;
+        struct Result;
+        struct Option;
+        struct Vec;
+        struct String;
+        struct Default;
+
+        #[derive(Debug, PartialEq, ToJson, FromJson)]
+        pub struct Record {
+            pub id: u32,
+            #[json(default)]
+            pub note: ::std::string::String,
+            #[json(skip)]
+            pub cached: u8,
+        }
+
+        #[derive(Debug, PartialEq, ToJson, FromJson)]
+        pub enum External {
+            Unit,
+            Pair(u8, u8),
+        }
+
+        #[derive(Debug, PartialEq, ToJson, FromJson)]
+        #[json(tag = "kind")]
+        pub enum Internal {
+            Unit,
+            Named { value: u8 },
+        }
+
+        #[derive(Debug, PartialEq, ToJson, FromJson)]
+        #[json(untagged)]
+        pub enum Untagged {
+            Number(u8),
+            Named { value: u8 },
+        }
+    }
+
+    #[derive(Debug, PartialEq, ToJson, FromJson)]
+    #[json(untagged)]
+    enum Id {
+        Number(u64),
+        Name(String),
+        Pair { prefix: String, number: u64 },
+        Missing,
+    }
+
+    fn round_trip<T: ToJson + FromJson + PartialEq + fmt::Debug>(value: T, json: &str) {
+        assert_eq!(value.to_json().to_string(), json);
+        assert_eq!(T::from_json(&parse(json).unwrap()), Ok(value));
+    }
+
+    #[test]
+    fn test_derive_enums() {
+        round_trip(Shape::Empty, r#""Empty""#);
+        round_trip(
+            Shape::Circle { radius: 2.5 },
+            r#"{"circle":{"radius":2.5}}"#,
+        );
+        round_trip(Shape::Square(Meters(3.0)), r#"{"Square":3.0}"#);
+        round_trip(
+            Shape::Line(Point(0, 0), Point(1, 1)),
+            r#"{"Line":[[0,0],[1,1]]}"#,
+        );
+
+        round_trip(Message::Ping, r#"{"kind":"Ping"}"#);
+        round_trip(
+            Message::Text {
+                body: "hi".to_string(),
+                to: None,
+            },
+            r#"{"kind":"Text","body":"hi","to":null}"#,
+        );
+        round_trip(
+            Message::Join {
+                name: "An".to_string(),
+                room: 3,
+            },
+            r#"{"kind":"Join","name":"An","room":3}"#,
+        );
+        round_trip(
+            Shadow::Field {
+                object: 1,
+                value: 2,
+            },
+            r#"{"Field":{"object":1,"value":2}}"#,
+        );
+
+        round_trip(
+            prelude::Record {
+                id: 1,
+                note: String::new(),
+                cached: 0,
+            },
+            r#"{"id":1,"note":""}"#,
+        );
+        round_trip(prelude::External::Unit, r#""Unit""#);
+        round_trip(prelude::External::Pair(1, 2), r#"{"Pair":[1,2]}"#);
+        round_trip(prelude::Internal::Unit, r#"{"kind":"Unit"}"#);
+        round_trip(
+            prelude::Internal::Named { value: 1 },
+            r#"{"kind":"Named","value":1}"#,
+        );
+        round_trip(prelude::Untagged::Number(1), "1");
+        round_trip(prelude::Untagged::Named { value: 1 }, r#"{"value":1}"#);
+
+        round_trip(Id::Number(7), "7");
+        round_trip(Id::Name("x".to_string()), r#""x""#);
+        round_trip(
+            Id::Pair {
+                prefix: "a".to_string(),
+                number: 1,
+            },
+            r#"{"prefix":"a","number":1}"#,
+        );
+        round_trip(Id::Missing, "null");
+    }
+
+    #[test]
+    fn test_derive_enum_errors() {
+        let err = Shape::from_json(&parse(r#""Triangle""#).unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown variant `Triangle`, expected one of `Empty`, `circle`, `Square`, `Line`"
+        );
+        let err =
+            Shape::from_json(&parse(r#"{"circle": {"radius": "big"}}"#).unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected number, found string at `/circle/radius`"
+        );
+
+        let err = Message::from_json(&parse(r#"{"body": "hi"}"#).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `kind`");
+        let err = Message::from_json(&parse(r#"{"kind": "Text"}"#).unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `body`");
+
+        let err = Id::from_json(&parse("[1]").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "data did not match any variant of untagged enum `Id`"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::error::Error;
+use std::fmt;
+
+// vị trí trong input: byte offset (từ 0), line và column (từ 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {} (byte {})",
+            self.line, self.column, self.offset
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnexpectedChar,
+    UnexpectedEof,
+    InvalidNumber,
+    NumberOutOfRange,
+    InvalidEscape,
+    InvalidUnicodeEscape,
+    LoneSurrogate,
+    ControlCharacter,
+    TrailingCharacters,
+    DuplicateKey,
+    InvalidUtf8,
+    Io(std::io::ErrorKind),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ErrorKind::Io(kind) => return write!(f, "I/O error: {}", kind),
+            ErrorKind::UnexpectedChar => "unexpected character",
+            ErrorKind::UnexpectedEof => "unexpected end of input",
+            ErrorKind::InvalidNumber => "invalid number",
+            ErrorKind::NumberOutOfRange => "number out of range",
+            ErrorKind::InvalidEscape => "invalid escape sequence",
+            ErrorKind::InvalidUnicodeEscape => "invalid unicode escape",
+            ErrorKind::LoneSurrogate => "lone surrogate in string",
+            ErrorKind::ControlCharacter => "control character in string",
+            ErrorKind::TrailingCharacters => "trailing characters after value",
+            ErrorKind::DuplicateKey => "duplicate key",
+            ErrorKind::InvalidUtf8 => "invalid UTF-8",
+        };
+        f.write_str(message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    kind: ErrorKind,
+    expected: Option<String>,
+    found: Option<char>,
+    position: Position,
+    // vị trí liên quan, ví dụ nơi key bị trùng xuất hiện lần đầu
+    previous: Option<Position>,
+}
+
+impl ParseError {
+    pub fn new(kind: ErrorKind, position: Position) -> Self {
+        ParseError {
+            kind,
+            expected: None,
+            found: None,
+            position,
+            previous: None,
+        }
+    }
+
+    pub fn with_expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    pub fn with_found(mut self, found: Option<char>) -> Self {
+        self.found = found;
+        self
+    }
+
+    pub fn with_previous(mut self, previous: Position) -> Self {
+        self.previous = Some(previous);
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    // mô tả token mong đợi, ví dụ "`:`" hoặc "a value"
+    pub fn expected(&self) -> Option<&str> {
+        self.expected.as_deref()
+    }
+
+    // None khi đã tới cuối input
+    pub fn found(&self) -> Option<char> {
+        self.found
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn previous(&self) -> Option<Position> {
+        self.previous
+    }
+
+    pub fn offset(&self) -> usize {
+        self.position.offset
+    }
+
+    pub fn line(&self) -> usize {
+        self.position.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.position.column
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(expected) = &self.expected {
+            write!(f, ": expected {}", expected)?;
+            match self.found {
+                Some(c) => write!(f, ", found `{}`", c.escape_debug())?,
+                None if self.kind == ErrorKind::UnexpectedEof => {
+                    f.write_str(", found end of input")?
+                }
+                None => {}
+            }
+        }
+        write!(f, " at {}", self.position)?;
+        if let Some(previous) = self.previous {
+            write!(f, ", first defined at {}", previous)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,319 @@
+// pull parser: đọc document thành 1 chuỗi event thay vì dựng cả cây Value,
+// để lọc / tổng hợp document rất lớn mà không tốn bộ nhớ
+use std::borrow::Cow;
+use std::io::Read;
+
+use crate::error::{ErrorKind, ParseError, Position};
+use crate::{
+    match_char, match_literal, read_key, read_number, read_string, Lexer, Number, ParseOptions,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'a> {
+    StartObject,
+    Key(Cow<'a, str>),
+    EndObject,
+    StartArray,
+    EndArray,
+    String(Cow<'a, str>),
+    Number(Number),
+    Bool(bool),
+    Null,
+}
+
+// đang đứng ở đâu trong 1 object / array
+#[derive(Debug, Clone, Copy)]
+enum Frame {
+    // ngay sau `{`: key hoặc `}`
+    ObjectStart,
+    // sau key: `:` rồi value
+    ObjectValue,
+    // sau value: `,` hoặc `}`
+    ObjectNext,
+    // ngay sau `[`: value hoặc `]`
+    ArrayStart,
+    // sau value: `,` hoặc `]`
+    ArrayNext,
+}
+
+pub struct Events<'a> {
+    src: Lexer<'a>,
+    stack: Vec<Frame>,
+    // top-level value đã bắt đầu chưa
+    started: bool,
+    // hết document hoặc đã gặp lỗi
+    finished: bool,
+    // vị trí bắt đầu của event vừa trả về
+    position: Position,
+}
+
+impl<'a> Events<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Events::from_lexer(Lexer::new(input))
+    }
+
+    pub fn with_options(input: &'a str, options: ParseOptions) -> Self {
+        Events::from_lexer(Lexer::with_options(input, options))
+    }
+
+    pub fn from_reader(reader: impl Read + 'a, options: ParseOptions) -> Self {
+        Events::from_lexer(Lexer::from_reader(reader, options))
+    }
+
+    pub fn from_lexer(src: Lexer<'a>) -> Self {
+        Events {
+            src,
+            stack: Vec::new(),
+            started: false,
+            finished: false,
+            position: Position::default(),
+        }
+    }
+
+    pub fn options(&self) -> &ParseOptions {
+        self.src.options()
+    }
+
+    // vị trí bắt đầu của event vừa đọc, ví dụ dấu `"` mở đầu 1 key
+    pub fn event_position(&self) -> Position {
+        self.position
+    }
+
+    // độ sâu hiện tại: số object / array đang mở
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    fn next_event(&mut self) -> Result<Option<Event<'a>>, ParseError> {
+        self.src.skip_trivia()?;
+        self.position = self.src.position();
+
+        let frame = match self.stack.last_mut() {
+            Some(frame) => frame,
+            None if self.started => {
+                // only whitespace may follow the top-level value
+                if self.src.peek().is_some() {
+                    return Err(self
+                        .src
+                        .error(ErrorKind::TrailingCharacters)
+                        .with_expected("end of input"));
+                }
+                return Ok(None);
+            }
+            None => {
+                self.started = true;
+                if self.src.options().object_only && self.src.peek() != Some('{') {
+                    return Err(self.src.unexpected("`{`"));
+                }
+                return self.value().map(Some);
+            }
+        };
+
+        let event = match *frame {
+            Frame::ObjectStart | Frame::ObjectNext => {
+                let closed = self.src.next_if_eq('}').is_some();
+                if closed {
+                    self.stack.pop();
+                    Event::EndObject
+                } else {
+                    if let Frame::ObjectNext = *frame {
+                        if self.src.next_if_eq(',').is_none() {
+                            return Err(self.src.unexpected("`,` or `}`"));
+                        }
+                        self.src.skip_trivia()?;
+                        self.position = self.src.position();
+                        if self.src.options().trailing_commas && self.src.next_if_eq('}').is_some()
+                        {
+                            self.stack.pop();
+                            return Ok(Some(Event::EndObject));
+                        }
+                    }
+                    *frame = Frame::ObjectValue;
+                    Event::Key(read_key(&mut self.src)?)
+                }
+            }
+            Frame::ObjectValue => {
+                *frame = Frame::ObjectNext;
+                match_char(&mut self.src, ':')?;
+                self.src.skip_trivia()?;
+                self.position = self.src.position();
+                self.value()?
+            }
+            Frame::ArrayStart | Frame::ArrayNext => {
+                let closed = self.src.next_if_eq(']').is_some();
+                if closed {
+                    self.stack.pop();
+                    Event::EndArray
+                } else {
+                    if let Frame::ArrayNext = *frame {
+                        if self.src.next_if_eq(',').is_none() {
+                            return Err(self.src.unexpected("`,` or `]`"));
+                        }
+                        self.src.skip_trivia()?;
+                        self.position = self.src.position();
+                        if self.src.options().trailing_commas && self.src.next_if_eq(']').is_some()
+                        {
+                            self.stack.pop();
+                            return Ok(Some(Event::EndArray));
+                        }
+                    } else {
+                        *frame = Frame::ArrayNext;
+                    }
+                    self.value()?
+                }
+            }
+        };
+
+        Ok(Some(event))
+    }
+
+    // event đầu tiên của 1 value
+    fn value(&mut self) -> Result<Event<'a>, ParseError> {
+        let src = &mut self.src;
+        match src.peek() {
+            Some('{') => {
+                src.next_char();
+                self.stack.push(Frame::ObjectStart);
+                Ok(Event::StartObject)
+            }
+            Some('[') => {
+                src.next_char();
+                self.stack.push(Frame::ArrayStart);
+                Ok(Event::StartArray)
+            }
+            Some('"') => read_string(src).map(Event::String),
+            Some('\'') if src.options().single_quotes => read_string(src).map(Event::String),
+            Some(c) if starts_number(src.options(), c) => read_number(src).map(Event::Number),
+            Some('t') => match_literal(src, "true").map(|_| Event::Bool(true)),
+            Some('f') => match_literal(src, "false").map(|_| Event::Bool(false)),
+            Some('n') => match_literal(src, "null").map(|_| Event::Null),
+            _ => Err(src.unexpected("a value")),
+        }
+    }
+}
+
+// `+`, `Infinity` và `NaN` chỉ mở đầu 1 number khi được bật
+fn starts_number(options: &ParseOptions, c: char) -> bool {
+    match c {
+        '-' | '0'..='9' => true,
+        '+' => options.leading_plus,
+        'I' | 'N' => options.infinity_nan,
+        _ => false,
+    }
+}
+
+impl<'a> Iterator for Events<'a> {
+    type Item = Result<Event<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let res = self.next_event();
+        // a read error looks like the end of input to the parser,
+        // so report it instead of whatever the parser saw there
+        let res = match self.src.input_error() {
+            Some(err) => Err(err),
+            None => res,
+        };
+        match res {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(err) => {
+                self.finished = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(input: &str) -> Result<Vec<Event<'_>>, ParseError> {
+        Events::new(input).collect()
+    }
+
+    #[test]
+    fn test_events() {
+        let events = collect(r#" {"a": [1, "x", true], "b": {}, "c": null} "#).unwrap();
+        assert_eq!(
+            events,
+            [
+                Event::StartObject,
+                Event::Key("a".into()),
+                Event::StartArray,
+                Event::Number(Number::Int(1)),
+                Event::String("x".into()),
+                Event::Bool(true),
+                Event::EndArray,
+                Event::Key("b".into()),
+                Event::StartObject,
+                Event::EndObject,
+                Event::Key("c".into()),
+                Event::Null,
+                Event::EndObject,
+            ]
+        );
+        assert_eq!(collect("[]").unwrap(), [Event::StartArray, Event::EndArray]);
+        // string đọc từ &str được mượn, đọc từ io::Read thì phải copy
+        let events = collect(r#"["a", "\u0062"]"#).unwrap();
+        assert!(matches!(events[1], Event::String(Cow::Borrowed("a"))));
+        assert!(matches!(events[2], Event::String(Cow::Owned(_))));
+        let mut events = Events::from_reader(&br#""a""#[..], ParseOptions::default());
+        assert!(matches!(
+            events.next(),
+            Some(Ok(Event::String(Cow::Owned(_))))
+        ));
+        assert_eq!(collect("false").unwrap(), [Event::Bool(false)]);
+    }
+
+    #[test]
+    fn test_events_errors() {
+        let mut events = Events::new("[1 2]");
+        assert_eq!(events.next(), Some(Ok(Event::StartArray)));
+        assert_eq!(events.next(), Some(Ok(Event::Number(Number::Int(1)))));
+        let err = events.next().unwrap().unwrap_err();
+        assert_eq!(err.expected(), Some("`,` or `]`"));
+        assert_eq!(err.offset(), 3);
+        // dừng hẳn sau lỗi đầu tiên
+        assert_eq!(events.next(), None);
+
+        assert!(collect(r#"{"a" 1}"#).is_err());
+        assert!(collect(r#"{"a":1,}"#).is_err());
+        assert!(collect("[1,]").is_err());
+        assert!(collect("[] []").is_err());
+        assert!(collect("").is_err());
+    }
+
+    #[test]
+    fn test_event_positions() {
+        let mut events = Events::new("{\n  \"key\": [true]\n}");
+        let mut positions = vec![];
+        while let Some(event) = events.next() {
+            event.unwrap();
+            positions.push((events.event_position().line, events.event_position().column));
+        }
+        assert_eq!(
+            positions,
+            [(1, 1), (2, 3), (2, 10), (2, 11), (2, 15), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn test_sum_without_tree() {
+        // cộng dồn mọi number mà không dựng Value
+        let total: i64 = Events::new(r#"{"a": [1, 2, {"b": 3}], "c": "4"}"#)
+            .filter_map(|event| match event {
+                Ok(Event::Number(n)) => n.as_i64(),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(total, 6);
+    }
+}
@@ -0,0 +1,146 @@
+use std::io::Read;
+
+use crate::error::{ErrorKind, ParseError, Position};
+use crate::read::{Input, IoReader};
+use crate::ParseOptions;
+
+// đọc từng char của input và ghi nhớ vị trí hiện tại,
+// để khi lỗi có thể báo đúng line/column
+pub struct Lexer<'a> {
+    input: Input<'a>,
+    // char đã peek nhưng chưa consume
+    peeked: Option<char>,
+    position: Position,
+    options: ParseOptions,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Lexer::with_options(input, ParseOptions::default())
+    }
+
+    pub fn with_options(input: &'a str, options: ParseOptions) -> Self {
+        Lexer::from_input(Input::Str(input, input.chars()), options)
+    }
+
+    // đọc dần từ `reader`, không cần có cả document trong bộ nhớ
+    pub fn from_reader(reader: impl Read + 'a, options: ParseOptions) -> Self {
+        Lexer::from_input(Input::Reader(IoReader::new(reader)), options)
+    }
+
+    fn from_input(input: Input<'a>, options: ParseOptions) -> Self {
+        Lexer {
+            input,
+            peeked: None,
+            position: Position::default(),
+            options,
+        }
+    }
+
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    // vị trí của char sắp đọc
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn peek(&mut self) -> Option<char> {
+        if self.peeked.is_none() {
+            self.peeked = self.input.next_char();
+        }
+        self.peeked
+    }
+
+    pub fn next_char(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.peeked = None;
+        self.advance(c);
+        Some(c)
+    }
+
+    pub fn next_if(&mut self, func: impl FnOnce(&char) -> bool) -> Option<char> {
+        let c = self.peek()?;
+        if !func(&c) {
+            return None;
+        }
+        self.peeked = None;
+        self.advance(c);
+        Some(c)
+    }
+
+    pub fn next_if_eq(&mut self, expected: char) -> Option<char> {
+        self.next_if(|c| *c == expected)
+    }
+
+    // whitespace is only allowed between tokens (RFC 8259, section 2):
+    // ' ': space
+    // \t: tab
+    // \n : line breaker
+    // \r: carriage return
+    // inside a string it is part of the value and is kept as written
+    pub fn skip_whitespace(&mut self) {
+        while self
+            .next_if(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+            .is_some()
+        {}
+    }
+
+    // whitespace và, khi bật `comments`, các comment `//` / `/* */` xen giữa
+    pub fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        while self.options.comments && self.next_if_eq('/').is_some() {
+            if self.next_if_eq('/').is_some() {
+                while self.next_if(|c| *c != '\n').is_some() {}
+            } else if self.next_if_eq('*').is_some() {
+                loop {
+                    match self.next_char() {
+                        Some('*') if self.next_if_eq('/').is_some() => break,
+                        Some(_) => {}
+                        None => return Err(self.unexpected("`*/`")),
+                    }
+                }
+            } else {
+                return Err(self.unexpected("`/` or `*`"));
+            }
+            self.skip_whitespace();
+        }
+        Ok(())
+    }
+
+    // đoạn input giữa 2 byte offset, None khi đọc từ io::Read
+    pub fn slice(&self, start: usize, end: usize) -> Option<&'a str> {
+        self.input.slice(start, end)
+    }
+
+    // lỗi I/O hoặc UTF-8 khiến input kết thúc sớm, tại vị trí hiện tại
+    pub fn input_error(&self) -> Option<ParseError> {
+        let kind = self.input.error()?;
+        Some(ParseError::new(kind, self.position))
+    }
+
+    // lỗi tại char sắp đọc
+    pub fn error(&mut self, kind: ErrorKind) -> ParseError {
+        ParseError::new(kind, self.position).with_found(self.peek())
+    }
+
+    // char sắp đọc không phải là `expected`
+    pub fn unexpected(&mut self, expected: &str) -> ParseError {
+        let kind = match self.peek() {
+            Some(_) => ErrorKind::UnexpectedChar,
+            None => ErrorKind::UnexpectedEof,
+        };
+        self.error(kind).with_expected(expected)
+    }
+
+    fn advance(&mut self, c: char) {
+        self.position.offset += c.len_utf8();
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+    }
+}
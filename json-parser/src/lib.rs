@@ -1,129 +1,582 @@
 // Implement JSON PARSER
+// để code sinh ra bởi json-parser-derive (`::json_parser::...`) dùng được trong crate này
+extern crate self as json_parser;
+
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::iter::Peekable;
-use std::str::Chars;
+use std::hash::Hash;
+use std::io::Read;
+
+#[macro_use]
+mod macros;
+
+mod access;
+pub mod borrowed;
+mod convert;
+mod error;
+mod events;
+mod lexer;
+mod map;
+mod merge;
+mod number;
+mod options;
+mod patch;
+mod path;
+mod pointer;
+mod pretty;
+mod read;
+mod report;
+mod schema;
+mod ser;
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub use access::ValueIndex;
+pub use convert::{FromJson, FromJsonError, ToJson};
+pub use error::{ErrorKind, ParseError, Position};
+pub use events::{Event, Events};
+pub use lexer::Lexer;
+pub use map::Map;
+pub use merge::{create_merge_patch, merge_patch};
+pub use number::Number;
+pub use options::{DuplicateKeys, ParseOptions};
+pub use patch::{apply_patch, diff, Operation, Patch, PatchError};
+pub use path::{JsonPath, PathError};
+pub use pointer::PointerError;
+pub use pretty::{to_string_pretty, Indent, PrettyConfig};
+pub use report::{compare, Change, Report};
+pub use schema::{Schema, SchemaError, Violation};
+pub use ser::to_string;
 
-#[derive(PartialEq, Debug)]
+#[cfg(feature = "derive")]
+pub use json_parser_derive::{FromJson, ToJson};
+
+#[derive(PartialEq, Debug, Clone)]
 pub enum Value {
-    Number(i64),
+    Null,
+    Number(Number),
     True,
     False,
     String(String),
     Array(Vec<Value>),
-    Object(HashMap<String, Value>),
+    Object(Map<String, Value>),
+}
+
+impl Value {
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) => n.as_u64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => n.as_f64(),
+            _ => None,
+        }
+    }
 }
 
-pub fn parse(input: &str) -> Result<Value, &'static str> {
-    // concat
-    // \n : line breaker
-    // \t: tab
-    // ' ': space
-    let removed_whitespace: String = input.chars().filter(|c| !c.is_whitespace()).collect();
-    let mut src = removed_whitespace.chars().peekable();
+pub fn parse(input: &str) -> Result<Value, ParseError> {
+    parse_with(input, &ParseOptions::default())
+}
+
+pub fn parse_with(input: &str, options: &ParseOptions) -> Result<Value, ParseError> {
+    parse_document(&mut Events::with_options(input, options.clone()))
+}
+
+// đọc từ file, socket, ... qua 1 buffer nhỏ thay vì load hết vào 1 String
+pub fn parse_reader<R: Read>(reader: R) -> Result<Value, ParseError> {
+    parse_reader_with(reader, &ParseOptions::default())
+}
 
-    parse_object(&mut src)
+pub fn parse_reader_with<R: Read>(reader: R, options: &ParseOptions) -> Result<Value, ParseError> {
+    parse_document(&mut Events::from_reader(reader, options.clone()))
 }
 
-// đầu vào là 1 string -> &str -> chars()
+// đầu vào là 1 string -> &str -> Lexer
 // match character is correct or not
 // error handling
-pub fn match_char(src: &mut Peekable<Chars>, expected: char) -> Result<(), &'static str> {
-    if src.next_if_eq(&expected).is_none() {
-        Err("Can not match correct char")
+pub fn match_char(src: &mut Lexer, expected: char) -> Result<(), ParseError> {
+    if src.next_if_eq(expected).is_none() {
+        Err(src.unexpected(&format!("`{}`", expected)))
     } else {
         Ok(())
     }
 }
 
-pub fn parse_string(src: &mut Peekable<Chars>) -> Result<Value, &'static str> {
-    match_char(src, '"')?;
-    let mut res = String::new();
+pub fn parse_string(src: &mut Lexer) -> Result<Value, ParseError> {
+    read_string(src).map(|s| Value::String(s.into_owned()))
+}
 
-    while let Some(c) = src.next_if(|c| *c != '"') {
-        res.push(c);
+// string không có escape được mượn thẳng từ input (khi input là &str),
+// chỉ copy ra String khi phải decode escape hoặc khi đọc từ io::Read
+pub(crate) fn read_string<'a>(src: &mut Lexer<'a>) -> Result<Cow<'a, str>, ParseError> {
+    // 'string' khi bật `single_quotes`, phải đóng bằng đúng dấu đã mở
+    let quote = match src.peek() {
+        Some('\'') if src.options().single_quotes => '\'',
+        _ => '"',
+    };
+    match_char(src, quote)?;
+    let start = src.position().offset;
+    let mut owned = match src.slice(start, start) {
+        Some(_) => None,
+        None => Some(String::new()),
+    };
+
+    loop {
+        match src.peek() {
+            Some(c) if c == quote => {
+                let end = src.position().offset;
+                src.next_char();
+                return Ok(match owned {
+                    Some(res) => Cow::Owned(res),
+                    None => Cow::Borrowed(src.slice(start, end).unwrap_or_default()),
+                });
+            }
+            Some('\\') => {
+                let end = src.position().offset;
+                let res = owned
+                    .get_or_insert_with(|| src.slice(start, end).unwrap_or_default().to_string());
+                res.push(parse_escape(src)?);
+            }
+            // U+0000 through U+001F must be escaped (RFC 8259, section 7)
+            Some(c) if c < '\u{20}' => return Err(src.error(ErrorKind::ControlCharacter)),
+            Some(c) => {
+                src.next_char();
+                if let Some(res) = &mut owned {
+                    res.push(c);
+                }
+            }
+            None => return Err(src.unexpected(&format!("`{}`", quote))),
+        }
     }
+}
 
-    match_char(src, '"')?;
+// key của object: string, hoặc identifier khi bật `unquoted_keys`
+pub(crate) fn read_key<'a>(src: &mut Lexer<'a>) -> Result<Cow<'a, str>, ParseError> {
+    match src.peek() {
+        Some(c) if src.options().unquoted_keys && is_identifier_start(c) => {
+            Ok(read_identifier(src))
+        }
+        _ => read_string(src),
+    }
+}
 
-    Ok(Value::String(res))
+fn is_identifier_start(c: char) -> bool {
+    c == '_' || c == '$' || c.is_alphabetic()
 }
 
-pub fn parse_number(src: &mut Peekable<Chars>) -> Result<Value, &'static str> {
+// identifier kiểu JavaScript (không hỗ trợ escape \uXXXX trong tên)
+fn read_identifier<'a>(src: &mut Lexer<'a>) -> Cow<'a, str> {
+    let start = src.position().offset;
     let mut res = String::new();
-
-    while let Some(c) = src.next_if(|c| c.is_numeric()) {
+    while let Some(c) = src.next_if(|c| is_identifier_start(*c) || c.is_alphanumeric()) {
         res.push(c);
     }
+    match src.slice(start, src.position().offset) {
+        Some(s) => Cow::Borrowed(s),
+        None => Cow::Owned(res),
+    }
+}
 
-    Ok(Value::Number(res.parse().expect("Can not parse to number")))
+// decode an escape sequence starting at `\`
+fn parse_escape(src: &mut Lexer) -> Result<char, ParseError> {
+    let start = src.position();
+    match_char(src, '\\')?;
+
+    let c = match src.peek() {
+        Some('"') => '"',
+        Some('\\') => '\\',
+        Some('/') => '/',
+        Some('b') => '\u{8}',
+        Some('f') => '\u{c}',
+        Some('n') => '\n',
+        Some('r') => '\r',
+        Some('t') => '\t',
+        Some('\'') if src.options().single_quotes => '\'',
+        Some('u') => {
+            src.next_char();
+            return parse_unicode_escape(src, start);
+        }
+        _ => return Err(src.error(ErrorKind::InvalidEscape)),
+    };
+    src.next_char();
+
+    Ok(c)
+}
+
+// \uXXXX, characters outside the BMP are written as a UTF-16 surrogate pair
+fn parse_unicode_escape(src: &mut Lexer, start: Position) -> Result<char, ParseError> {
+    let lone_surrogate = || ParseError::new(ErrorKind::LoneSurrogate, start);
+
+    let code = match parse_hex4(src)? {
+        high @ 0xD800..=0xDBFF => {
+            if src.next_if_eq('\\').is_none() || src.next_if_eq('u').is_none() {
+                return Err(lone_surrogate());
+            }
+            let low = parse_hex4(src)?;
+            if !(0xDC00..=0xDFFF).contains(&low) {
+                return Err(lone_surrogate());
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        }
+        0xDC00..=0xDFFF => return Err(lone_surrogate()),
+        code => code,
+    };
+
+    char::from_u32(code).ok_or_else(|| ParseError::new(ErrorKind::InvalidUnicodeEscape, start))
+}
+
+fn parse_hex4(src: &mut Lexer) -> Result<u32, ParseError> {
+    let mut code = 0;
+    for _ in 0..4 {
+        let digit = match src.peek().and_then(|c| c.to_digit(16)) {
+            Some(digit) => digit,
+            None => {
+                return Err(src
+                    .error(ErrorKind::InvalidUnicodeEscape)
+                    .with_expected("a hex digit"))
+            }
+        };
+        src.next_char();
+        code = code * 16 + digit;
+    }
+    Ok(code)
 }
 
-pub fn parse_bool(src: &mut Peekable<Chars>) -> Result<Value, &'static str> {
+pub fn parse_number(src: &mut Lexer) -> Result<Value, ParseError> {
+    read_number(src).map(Value::Number)
+}
+
+// number = [ minus ] int [ frac ] [ exp ]   (RFC 8259, section 6)
+pub(crate) fn read_number(src: &mut Lexer) -> Result<Number, ParseError> {
+    let start = src.position();
     let mut res = String::new();
+    let mut is_float = false;
+
+    let options = src.options();
+    let (leading_plus, hex_numbers, infinity_nan) = (
+        options.leading_plus,
+        options.hex_numbers,
+        options.infinity_nan,
+    );
+
+    if let Some(c) = src.next_if(|c| *c == '-' || (leading_plus && *c == '+')) {
+        if c == '-' {
+            res.push(c);
+        }
+    }
+    let negative = !res.is_empty();
+
+    if infinity_nan {
+        match src.peek() {
+            Some('I') => {
+                match_literal(src, "Infinity")?;
+                let inf = if negative {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                };
+                return Ok(Number::Float(inf));
+            }
+            Some('N') => {
+                match_literal(src, "NaN")?;
+                return Ok(Number::Float(f64::NAN));
+            }
+            _ => {}
+        }
+    }
+
+    // int = zero / ( digit1-9 *DIGIT )
+    match src.next_if(|c| c.is_ascii_digit()) {
+        Some('0') => {
+            res.push('0');
+            if hex_numbers && src.next_if(|c| *c == 'x' || *c == 'X').is_some() {
+                return read_hex(src, start, negative);
+            }
+            if src.peek().is_some_and(|c| c.is_ascii_digit()) {
+                // leading zeros are not allowed
+                return Err(src.error(ErrorKind::InvalidNumber));
+            }
+        }
+        Some(c) => {
+            res.push(c);
+            parse_digits(src, &mut res);
+        }
+        None => return Err(invalid_number(src)),
+    }
+
+    // frac = decimal-point 1*DIGIT
+    if let Some(c) = src.next_if_eq('.') {
+        is_float = true;
+        res.push(c);
+        if parse_digits(src, &mut res) == 0 {
+            return Err(invalid_number(src));
+        }
+    }
 
-    while let Some(c) = src.next_if(|c| *c != ',') {
+    // exp = e [ minus / plus ] 1*DIGIT
+    if let Some(c) = src.next_if(|c| *c == 'e' || *c == 'E') {
+        is_float = true;
         res.push(c);
+        if let Some(sign) = src.next_if(|c| *c == '-' || *c == '+') {
+            res.push(sign);
+        }
+        if parse_digits(src, &mut res) == 0 {
+            return Err(invalid_number(src));
+        }
     }
-    match res.as_str() {
-        "true" => Ok(Value::True),
-        "false" => Ok(Value::False),
-        _ => Err("Can not parse to bool"),
+
+    let out_of_range = || ParseError::new(ErrorKind::NumberOutOfRange, start);
+
+    if is_float {
+        let number: f64 = res.parse().map_err(|_| out_of_range())?;
+        if !number.is_finite() {
+            return Err(out_of_range());
+        }
+        Ok(Number::Float(number))
+    } else if let Ok(number) = res.parse::<i64>() {
+        Ok(Number::Int(number))
+    } else {
+        // only positive integers above i64::MAX can still fit in u64
+        res.parse::<u64>()
+            .map(Number::UInt)
+            .map_err(|_| out_of_range())
     }
 }
-pub fn parse_object(src: &mut Peekable<Chars>) -> Result<Value, &'static str> {
-    match_char(src, '{')?;
-    if src.next_if_eq(&'}').is_some() {
-        return Ok(Value::Object(HashMap::new()));
+
+// phần sau `0x`, luôn là số nguyên
+fn read_hex(src: &mut Lexer, start: Position, negative: bool) -> Result<Number, ParseError> {
+    let mut digits = String::new();
+    while let Some(c) = src.next_if(|c| c.is_ascii_hexdigit()) {
+        digits.push(c);
+    }
+    if digits.is_empty() {
+        return Err(src
+            .error(ErrorKind::InvalidNumber)
+            .with_expected("a hex digit"));
     }
 
-    let mut object = HashMap::new();
+    let out_of_range = || ParseError::new(ErrorKind::NumberOutOfRange, start);
+    let n = u64::from_str_radix(&digits, 16).map_err(|_| out_of_range())?;
+    if !negative {
+        return Ok(match i64::try_from(n) {
+            Ok(n) => Number::Int(n),
+            Err(_) => Number::UInt(n),
+        });
+    }
+    0i64.checked_sub_unsigned(n)
+        .map(Number::Int)
+        .ok_or_else(out_of_range)
+}
 
-    loop {
-        let key = parse_string(src)?;
-        match_char(src, ':')?;
-        let value = parse_value(src)?;
-        if let Value::String(k) = key {
-            object.insert(k, value);
+fn invalid_number(src: &mut Lexer) -> ParseError {
+    src.error(ErrorKind::InvalidNumber).with_expected("a digit")
+}
+
+// push every ascii digit into `res`, return how many were read
+fn parse_digits(src: &mut Lexer, res: &mut String) -> usize {
+    let mut count = 0;
+    while let Some(c) = src.next_if(|c| c.is_ascii_digit()) {
+        res.push(c);
+        count += 1;
+    }
+    count
+}
+
+pub fn parse_bool(src: &mut Lexer) -> Result<Value, ParseError> {
+    match src.peek() {
+        Some('t') => match_literal(src, "true").map(|_| Value::True),
+        Some('f') => match_literal(src, "false").map(|_| Value::False),
+        _ => Err(src.unexpected("`true` or `false`")),
+    }
+}
+
+pub fn parse_null(src: &mut Lexer) -> Result<Value, ParseError> {
+    match_literal(src, "null").map(|_| Value::Null)
+}
+
+// consume exactly the chars of `literal`, nothing after it
+pub(crate) fn match_literal(src: &mut Lexer, literal: &str) -> Result<(), ParseError> {
+    for expected in literal.chars() {
+        if src.next_if_eq(expected).is_none() {
+            return Err(src.unexpected(&format!("`{}`", literal)));
         }
-        if let Some(_) = src.next_if_eq(&',') {
-            continue;
-        } else {
-            break;
+    }
+    Ok(())
+}
+
+// parse_object / parse_array / parse_value dựng Value từ các event của `Events`
+
+pub fn parse_object(events: &mut Events) -> Result<Value, ParseError> {
+    match next_event(events)? {
+        Event::StartObject => build_object(events),
+        _ => Err(
+            ParseError::new(ErrorKind::UnexpectedChar, events.event_position())
+                .with_expected("`{`"),
+        ),
+    }
+}
+
+pub fn parse_array(events: &mut Events) -> Result<Value, ParseError> {
+    match next_event(events)? {
+        Event::StartArray => build_array(events),
+        _ => Err(
+            ParseError::new(ErrorKind::UnexpectedChar, events.event_position())
+                .with_expected("`[`"),
+        ),
+    }
+}
+
+pub fn parse_value(events: &mut Events) -> Result<Value, ParseError> {
+    let event = next_event(events)?;
+    build_value(events, event)
+}
+
+// cây được dựng từ event: `Value` hoặc `borrowed::Value<'a>`
+pub(crate) trait Build<'a>: Sized {
+    type Key: Hash + Eq + Clone;
+
+    fn key(key: Cow<'a, str>) -> Self::Key;
+    // String, Number, Bool hoặc Null
+    fn scalar(event: Event<'a>) -> Self;
+    fn array(items: Vec<Self>) -> Self;
+    fn object(object: Map<Self::Key, Self>) -> Self;
+}
+
+impl<'a> Build<'a> for Value {
+    type Key = String;
+
+    fn key(key: Cow<'a, str>) -> String {
+        key.into_owned()
+    }
+
+    fn scalar(event: Event<'a>) -> Value {
+        match event {
+            Event::String(s) => Value::String(s.into_owned()),
+            Event::Number(n) => Value::Number(n),
+            Event::Bool(true) => Value::True,
+            Event::Bool(false) => Value::False,
+            Event::Null => Value::Null,
+            _ => unreachable!("not a scalar event"),
         }
     }
 
-    Ok(Value::Object(object))
+    fn array(items: Vec<Value>) -> Value {
+        Value::Array(items)
+    }
+
+    fn object(object: Map<String, Value>) -> Value {
+        Value::Object(object)
+    }
 }
 
-pub fn parse_array(src: &mut Peekable<Chars>) -> Result<Value, &'static str> {
-    match_char(src, '[')?;
-    if src.next_if_eq(&']').is_some() {
-        return Ok(Value::Array(vec![]));
+// một value duy nhất, trước và sau chỉ có whitespace
+pub(crate) fn parse_document<'a, T: Build<'a>>(events: &mut Events<'a>) -> Result<T, ParseError> {
+    let event = next_event(events)?;
+    let value = build_value(events, event)?;
+    match events.next() {
+        Some(Err(err)) => Err(err),
+        // Events stops after the top-level value
+        _ => Ok(value),
     }
+}
 
-    let mut res = vec![];
+fn next_event<'a>(events: &mut Events<'a>) -> Result<Event<'a>, ParseError> {
+    match events.next() {
+        Some(res) => res,
+        None => Err(
+            ParseError::new(ErrorKind::UnexpectedEof, events.event_position())
+                .with_expected("a value"),
+        ),
+    }
+}
 
-    loop {
-        let value = parse_value(src)?;
-        res.push(value);
-        if let Some(_) = src.next_if_eq(&',') {
-            continue;
-        } else {
-            break;
+fn build_value<'a, T: Build<'a>>(
+    events: &mut Events<'a>,
+    event: Event<'a>,
+) -> Result<T, ParseError> {
+    match event {
+        Event::StartObject => build_object(events),
+        Event::StartArray => build_array(events),
+        Event::Key(_) | Event::EndObject | Event::EndArray => {
+            unreachable!("Events only yields these inside a container")
         }
+        scalar => Ok(T::scalar(scalar)),
     }
-    match_char(src, ']')?;
-    Ok(Value::Array(res))
 }
 
-pub fn parse_value(src: &mut Peekable<Chars>) -> Result<Value, &'static str> {
-    match src.peek() {
-        Some('{') => parse_object(src),
-        Some('"') => parse_string(src),
-        Some('[') => parse_array(src),
-        Some(c) if c.is_numeric() => parse_number(src),
-        _ => parse_bool(src),
+// sau StartObject: các cặp Key + value cho tới EndObject
+fn build_object<'a, T: Build<'a>>(events: &mut Events<'a>) -> Result<T, ParseError> {
+    let mut object = Map::new();
+    // where each key was first seen, for DuplicateKeys::Reject
+    let mut first_seen = HashMap::new();
+    // every value of each key in first-seen order, for DuplicateKeys::Collect
+    let mut collected: Map<T::Key, Vec<T>> = Map::new();
+
+    // Events only yields Key or EndObject here
+    while let Event::Key(k) = next_event(events)? {
+        let k = T::key(k);
+        let key_start = events.event_position();
+        let event = next_event(events)?;
+        let value = build_value(events, event)?;
+
+        match events.options().duplicate_keys {
+            DuplicateKeys::KeepLast => {
+                object.insert(k, value);
+            }
+            DuplicateKeys::KeepFirst => {
+                if !object.contains_key(&k) {
+                    object.insert(k, value);
+                }
+            }
+            DuplicateKeys::Reject => {
+                if let Some(&first) = first_seen.get(&k) {
+                    return Err(
+                        ParseError::new(ErrorKind::DuplicateKey, key_start).with_previous(first)
+                    );
+                }
+                first_seen.insert(k.clone(), key_start);
+                object.insert(k, value);
+            }
+            DuplicateKeys::Collect => match collected.get_mut(&k) {
+                Some(values) => values.push(value),
+                None => {
+                    collected.insert(k, vec![value]);
+                }
+            },
+        }
+    }
+
+    // chỉ có key khi dùng Collect, `object` vẫn rỗng nên giữ đúng thứ tự
+    for (k, values) in collected {
+        object.insert(k, T::array(values));
+    }
+
+    Ok(T::object(object))
+}
+
+// sau StartArray: các value cho tới EndArray
+fn build_array<'a, T: Build<'a>>(events: &mut Events<'a>) -> Result<T, ParseError> {
+    let mut res = vec![];
+
+    loop {
+        match next_event(events)? {
+            Event::EndArray => break,
+            event => res.push(build_value(events, event)?),
+        }
     }
+
+    Ok(T::array(res))
 }
 
 // viết unit tests
@@ -132,87 +585,501 @@ mod tests {
 
     use super::*;
 
+    // parse fixture bằng cả `parse` và `parse_reader`, kết quả phải giống nhau
+    fn parse_fixture(path: &str) -> Result<Value, ParseError> {
+        let src = std::fs::read_to_string(path).expect("Can not found test file");
+        let file = std::fs::File::open(path).expect("Can not found test file");
+        let parsed = parse(&src);
+        assert_eq!(parse_reader(file), parsed);
+        parsed
+    }
+
     #[test]
     fn test_1() {
-        let src = std::fs::read_to_string("tests/1.json").expect("Can not found test file");
-        let parsed = parse(&src);
-        let expectation = Value::Object(HashMap::new());
-        assert_eq!(parsed, Ok(expectation));
+        let parsed = parse_fixture("tests/1.json").unwrap();
+        crate::assert_json_eq!(parsed, json!({}));
     }
 
     #[test]
     fn test_2() {
-        let src = std::fs::read_to_string("tests/2.json").expect("Can not found test file");
-        let parsed = parse(&src);
-        let mut body = HashMap::new();
-        body.insert("key".to_string(), Value::String("value".to_string()));
-
-        let expectation = Value::Object(body);
-        assert_eq!(parsed, Ok(expectation));
+        let parsed = parse_fixture("tests/2.json").unwrap();
+        crate::assert_json_eq!(parsed, json!({ "key": "value" }));
     }
 
     #[test]
     fn test_3() {
-        let src = std::fs::read_to_string("tests/3.json").expect("Can not found test file");
-        let parsed = parse(&src);
-        let mut body = HashMap::new();
-        body.insert("key1".to_string(), Value::True);
-        body.insert("key2".to_string(), Value::False);
-        body.insert("key3".to_string(), Value::String("value".to_string()));
-        body.insert("key4".to_string(), Value::Number(101));
-
-        let expectation = Value::Object(body);
-        assert_eq!(parsed, Ok(expectation));
+        let parsed = parse_fixture("tests/3.json").unwrap();
+        let expectation = json!({
+            "key1": true,
+            "key2": false,
+            "key3": "value",
+            "key4": 101,
+        });
+        crate::assert_json_eq!(parsed, expectation);
     }
 
     #[test]
     fn test_4() {
-        let src = std::fs::read_to_string("tests/4.json").expect("Can not found test file");
-        let parsed = parse(&src);
-        let mut body = HashMap::new();
-        body.insert("key".to_string(), Value::String("value".to_string()));
-        body.insert("key1".to_string(), Value::Number(101));
-        body.insert("key2".to_string(), Value::Object(HashMap::new()));
-        body.insert("key3".to_string(), Value::Array(vec![]));
-
-        let expectation = Value::Object(body);
-        assert_eq!(parsed, Ok(expectation));
-    }
-    // {
-    //     "title": "Rust",
-    //     "year": 2023,
-    //     "live": true,
-    //     "organizers": ["vbi", "techfest"],
-    //     "presenter": {
-    //       "name": "Dung",
-    //       "age": 27,
-    //       "occupation": "Engineer"
-    //     }
-    // }
+        let parsed = parse_fixture("tests/4.json").unwrap();
+        let expectation = json!({
+            "key": "value",
+            "key1": 101,
+            "key2": {},
+            "key3": [],
+        });
+        crate::assert_json_eq!(parsed, expectation);
+    }
+
     #[test]
     fn test_5() {
-        let src = std::fs::read_to_string("tests/5.json").expect("Can not found test file");
-        let parsed = parse(&src);
-        let mut body = HashMap::new();
-        let payload_org = vec![
-            Value::String("vbi".to_string()),
-            Value::String("techfest".to_string()),
-        ];
-        let mut payload_presenter = HashMap::new();
-        payload_presenter.insert("name".to_string(), Value::String("Dung".to_string()));
-        payload_presenter.insert("age".to_string(), Value::Number(27));
-        payload_presenter.insert("occupation".to_string(), Value::String("Engineer".to_string()));
+        let parsed = parse_fixture("tests/5.json").unwrap();
+        let expectation = json!({
+            "title": "Rust",
+            "year": 2023,
+            "live": true,
+            "organizers": ["vbi", "techfest"],
+            "presenter": {
+                "name": "Dung",
+                "age": 27,
+                "occupation": "Engineer"
+            }
+        });
+        crate::assert_json_eq!(parsed, expectation);
+    }
 
-        body.insert("title".to_string(), Value::String("Rust".to_string()));
-        body.insert("year".to_string(), Value::Number(2023));
-        body.insert("live".to_string(), Value::True);
-        body.insert("organizers".to_string(), Value::Array(payload_org));
-        body.insert("presenter".to_string(), Value::Object(payload_presenter));
+    #[test]
+    fn test_literals() {
+        let src = r#"{"a":[true,false,null],"b":null}"#;
+        assert_eq!(
+            parse(src),
+            Ok(json!({ "a": [true, false, null], "b": null }))
+        );
+    }
 
-        let expectation = Value::Object(body);
-        assert_eq!(parsed, Ok(expectation));
+    #[test]
+    fn test_invalid_literals() {
+        assert!(parse(r#"{"a":nul}"#).is_err());
+        assert!(parse(r#"{"a":True}"#).is_err());
+        assert!(parse(r#"{"a":truex}"#).is_err());
+        assert!(parse(r#"{"a":[null,]}"#).is_err());
     }
-}
 
-// nhược 
-// ko kể dấu kí tự đặc biệt như \t, \n , ' ' , ..
+    #[test]
+    fn test_whitespace() {
+        let src = "{ \"occupation\" :\t\"Blockchain Engineer\" ,\r\n \"tags\": [ \" a b \" , 1 ] }";
+        let mut body = Map::new();
+        body.insert(
+            "occupation".to_string(),
+            Value::String("Blockchain Engineer".to_string()),
+        );
+        body.insert(
+            "tags".to_string(),
+            Value::Array(vec![
+                Value::String(" a b ".to_string()),
+                Value::Number(Number::Int(1)),
+            ]),
+        );
+
+        assert_eq!(parse(src), Ok(Value::Object(body)));
+    }
+
+    #[test]
+    fn test_invalid_whitespace() {
+        // only space, tab, CR and LF separate tokens
+        assert!(parse("{\u{a0}}").is_err());
+        assert!(parse("{\"a\":\u{b}1}").is_err());
+    }
+
+    #[test]
+    fn test_key_order() {
+        let src = r#"{"title":"Rust","year":2023,"live":true,"b":{"z":1,"a":2}}"#;
+        let mut value = parse(src).unwrap();
+        assert_eq!(to_string(&value), src);
+
+        if let Value::Object(object) = &mut value {
+            assert_eq!(
+                object.remove("year"),
+                Some(Value::Number(Number::Int(2023)))
+            );
+            object.insert("year".to_string(), Value::Null);
+            object.insert("title".to_string(), Value::String("JSON".to_string()));
+        }
+        assert_eq!(
+            to_string(&value),
+            r#"{"title":"JSON","live":true,"b":{"z":1,"a":2},"year":null}"#
+        );
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let src = "{\"a\": [1],\n \"b\": 2,\n \"a\": 3, \"a\": 4}";
+        let parse_policy = |duplicate_keys| {
+            let options = ParseOptions {
+                duplicate_keys,
+                ..Default::default()
+            };
+            parse_with(src, &options).map(|value| to_string(&value))
+        };
+
+        assert_eq!(
+            parse_policy(DuplicateKeys::KeepLast),
+            Ok(r#"{"a":4,"b":2}"#.to_string())
+        );
+        assert_eq!(
+            parse_policy(DuplicateKeys::KeepFirst),
+            Ok(r#"{"a":[1],"b":2}"#.to_string())
+        );
+        assert_eq!(
+            parse_policy(DuplicateKeys::Collect),
+            Ok(r#"{"a":[[1],3,4],"b":[2]}"#.to_string())
+        );
+        // key lặp lại và array viết sẵn không bị lẫn với nhau
+        let collect = ParseOptions {
+            duplicate_keys: DuplicateKeys::Collect,
+            ..Default::default()
+        };
+        assert_ne!(
+            parse_with(r#"{"a": 1, "a": 3}"#, &collect),
+            parse_with(r#"{"a": [1, 3]}"#, &collect)
+        );
+
+        let err = parse_policy(DuplicateKeys::Reject).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::DuplicateKey);
+        assert_eq!((err.line(), err.column()), (3, 2));
+        let first = err.previous().unwrap();
+        assert_eq!((first.line, first.column, first.offset), (1, 2, 1));
+        assert_eq!(
+            err.to_string(),
+            "duplicate key at line 3, column 2 (byte 21), first defined at line 1, column 2 (byte 1)"
+        );
+    }
+
+    // trả về từng byte một, để ký tự nhiều byte bị cắt giữa 2 lần read
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.0.split_first() {
+                Some((&b, rest)) if !buf.is_empty() => {
+                    buf[0] = b;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_reader() {
+        let src = r#"{"name": "Dũng 🦀", "tags": ["中文", 1.5]}"#;
+        let value = parse(src).unwrap();
+        assert_eq!(parse_reader(src.as_bytes()), Ok(value.clone()));
+        assert_eq!(parse_reader(ByteReader(src.as_bytes())), Ok(value));
+
+        let err = parse_reader(&b"{} x"[..]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TrailingCharacters);
+    }
+
+    #[test]
+    fn test_parse_reader_invalid_utf8() {
+        let err = parse_reader(ByteReader(b"[\"\xc3\xa9\xff\"]")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+        assert_eq!((err.offset(), err.column()), (4, 4));
+
+        // surrogate encoded directly in UTF-8
+        let err = parse_reader(&b"\n\"\xed\xa0\x80\""[..]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+        assert_eq!((err.line(), err.column()), (2, 2));
+
+        // input ends in the middle of a character
+        let err = parse_reader(&b"\"\xe4\xb8"[..]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+        assert_eq!(err.offset(), 1);
+    }
+
+    #[test]
+    fn test_parse_reader_io_error() {
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::ConnectionReset.into())
+            }
+        }
+
+        let err = parse_reader(std::io::Read::chain(&b"[1, "[..], Broken)).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Io(std::io::ErrorKind::ConnectionReset)
+        );
+        assert_eq!(err.offset(), 4);
+    }
+
+    #[test]
+    fn test_build_from_events() {
+        let mut events = Events::new(r#"[{"a": []}, 2]"#);
+        assert_eq!(events.next(), Some(Ok(Event::StartArray)));
+        // dựng riêng phần tử đầu tiên, phần còn lại vẫn đọc bằng event
+        let mut first = Map::new();
+        first.insert("a".to_string(), Value::Array(vec![]));
+        assert_eq!(parse_object(&mut events), Ok(Value::Object(first)));
+        assert_eq!(parse_value(&mut events), Ok(Value::Number(Number::Int(2))));
+        assert_eq!(events.next(), Some(Ok(Event::EndArray)));
+
+        let err = parse_array(&mut Events::new("{}")).unwrap_err();
+        assert_eq!(err.expected(), Some("`[`"));
+    }
+
+    #[test]
+    fn test_top_level_value() {
+        assert_eq!(
+            parse("[1,2]"),
+            Ok(Value::Array(vec![
+                Value::Number(Number::Int(1)),
+                Value::Number(Number::Int(2)),
+            ]))
+        );
+        assert_eq!(parse(r#""x""#), Ok(Value::String("x".to_string())));
+        assert_eq!(parse("42"), Ok(Value::Number(Number::Int(42))));
+        assert_eq!(parse(" null \n"), Ok(Value::Null));
+    }
+
+    #[test]
+    fn test_trailing_characters() {
+        let err = parse("{}{}").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TrailingCharacters);
+        assert_eq!(err.offset(), 2);
+
+        let err = parse("{} junk").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TrailingCharacters);
+        assert_eq!(err.found(), Some('j'));
+
+        assert!(parse("").is_err());
+        assert!(parse("1 2").is_err());
+    }
+
+    #[test]
+    fn test_relaxed() {
+        let src = r#"
+            // config viết tay
+            {
+                name: 'Rust "Challenge"',
+                $port: 0x1F90, /* 8080 */
+                'retries': +3,
+                ratio: -Infinity,
+                limit: NaN,
+                hosts: ['a', 'b\'c',],
+            }
+        "#;
+        let value = parse_with(src, &ParseOptions::relaxed()).unwrap();
+        assert_eq!(
+            to_string(&value),
+            r#"{"name":"Rust \"Challenge\"","$port":8080,"retries":3,"ratio":null,"limit":null,"hosts":["a","b'c"]}"#
+        );
+        assert_eq!(value["ratio"].as_f64(), Some(f64::NEG_INFINITY));
+        assert!(value["limit"].as_f64().unwrap().is_nan());
+        // đọc từ io::Read cũng giống hệt
+        assert_eq!(
+            to_string(&parse_reader_with(src.as_bytes(), &ParseOptions::relaxed()).unwrap()),
+            to_string(&value)
+        );
+
+        // strict vẫn là mặc định
+        assert!(parse(src).is_err());
+        assert_eq!(parse_with("[1]", &ParseOptions::relaxed()), parse("[1]"));
+    }
+
+    #[test]
+    fn test_relaxed_toggles() {
+        let relaxed = |src: &str, set: fn(&mut ParseOptions)| {
+            let mut options = ParseOptions::default();
+            set(&mut options);
+            assert!(parse(src).is_err(), "{} parsed in strict mode", src);
+            parse_with(src, &options).map(|value| to_string(&value))
+        };
+
+        let comments = |o: &mut ParseOptions| o.comments = true;
+        assert_eq!(
+            relaxed("/* a */ [1, // b\n 2] // c", comments),
+            Ok("[1,2]".to_string())
+        );
+        assert!(relaxed("[1, / 2]", comments).is_err());
+        let err = relaxed("[1 /* 2]", comments).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(err.expected(), Some("`*/`"));
+        // comment chỉ được nằm giữa các token, nên không ăn vào string
+        assert_eq!(
+            parse_with(r#""// a""#, &ParseOptions::relaxed()),
+            Ok(Value::String("// a".to_string()))
+        );
+
+        let trailing = |o: &mut ParseOptions| o.trailing_commas = true;
+        assert_eq!(
+            relaxed(r#"{"a": [1, 2,],}"#, trailing),
+            Ok(r#"{"a":[1,2]}"#.to_string())
+        );
+        assert!(relaxed("[1,,]", trailing).is_err());
+        assert!(parse_with("[,]", &ParseOptions::relaxed()).is_err());
+        assert!(parse_with("{,}", &ParseOptions::relaxed()).is_err());
+
+        let single = |o: &mut ParseOptions| o.single_quotes = true;
+        assert_eq!(
+            relaxed(r#"{'a': 'x"y\'z'}"#, single),
+            Ok(r#"{"a":"x\"y'z"}"#.to_string())
+        );
+        assert!(relaxed(r#"['a"]"#, single).is_err());
+
+        let unquoted = |o: &mut ParseOptions| o.unquoted_keys = true;
+        assert_eq!(
+            relaxed(r#"{_a1: 1, ký: 2, "b": 3}"#, unquoted),
+            Ok(r#"{"_a1":1,"ký":2,"b":3}"#.to_string())
+        );
+        assert!(relaxed("{1a: 1}", unquoted).is_err());
+
+        let hex = |o: &mut ParseOptions| o.hex_numbers = true;
+        assert_eq!(
+            relaxed("[0xff, -0X10, 0xFFFFFFFFFFFFFFFF]", hex),
+            Ok("[255,-16,18446744073709551615]".to_string())
+        );
+        assert!(relaxed("0x", hex).is_err());
+        assert_eq!(
+            relaxed("0x10000000000000000", hex).unwrap_err().kind(),
+            ErrorKind::NumberOutOfRange
+        );
+
+        let plus = |o: &mut ParseOptions| o.leading_plus = true;
+        assert_eq!(relaxed("[+1, +1.5e1]", plus), Ok("[1,15.0]".to_string()));
+        assert!(relaxed("+-1", plus).is_err());
+
+        let special = |o: &mut ParseOptions| o.infinity_nan = true;
+        assert_eq!(
+            relaxed("[Infinity, -NaN]", special),
+            Ok("[null,null]".to_string())
+        );
+        assert!(relaxed("Inf", special).is_err());
+        // 1 tuỳ chọn không kéo theo tuỳ chọn khác
+        assert!(relaxed("+Infinity", special).is_err());
+    }
+
+    #[test]
+    fn test_object_only() {
+        let options = ParseOptions {
+            object_only: true,
+            ..Default::default()
+        };
+        assert_eq!(parse_with(" {} ", &options), Ok(Value::Object(Map::new())));
+        assert!(parse_with("[1,2]", &options).is_err());
+        assert!(parse_with("{} []", &options).is_err());
+    }
+
+    #[test]
+    fn test_error_position() {
+        let err = parse("{\n  \"key\" 1}").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedChar);
+        assert_eq!(err.expected(), Some("`:`"));
+        assert_eq!(err.found(), Some('1'));
+        assert_eq!((err.offset(), err.line(), err.column()), (10, 2, 9));
+        assert_eq!(
+            err.to_string(),
+            "unexpected character: expected `:`, found `1` at line 2, column 9 (byte 10)"
+        );
+
+        let err = parse("{\"a\": [1, 2").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(err.found(), None);
+        assert_eq!(err.offset(), 11);
+
+        let err = parse(r#"{"ký": "\ud83e"}"#).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::LoneSurrogate);
+        assert_eq!((err.offset(), err.column()), (9, 9));
+
+        let err: Box<dyn std::error::Error> = Box::new(parse("{").unwrap_err());
+        assert!(err.to_string().contains("end of input"));
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let parse_str = |s: &str| parse_string(&mut Lexer::new(s));
+
+        assert_eq!(
+            parse_str(r#""a\"b\\c\/d\be\ff\ng\rh\ti""#),
+            Ok(Value::String("a\"b\\c/d\u{8}e\u{c}f\ng\rh\ti".to_string()))
+        );
+        assert_eq!(
+            parse_str(r#""\u0041\u00e9\u4e2d""#),
+            Ok(Value::String("Aé中".to_string()))
+        );
+        assert_eq!(
+            parse_str(r#""\ud83e\udd80""#),
+            Ok(Value::String("🦀".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_string() {
+        let parse_str = |s: &str| parse_string(&mut Lexer::new(s));
+
+        assert!(parse_str(r#""\ud83e""#).is_err());
+        assert!(parse_str(r#""\ud83eA""#).is_err());
+        assert!(parse_str(r#""\udd80""#).is_err());
+        assert!(parse_str(r#""\u12G4""#).is_err());
+        assert!(parse_str(r#""\x""#).is_err());
+        assert!(parse_str("\"a\nb\"").is_err());
+        assert!(parse_str("\"abc").is_err());
+    }
+
+    #[test]
+    fn test_number() {
+        let parse_str = |s: &str| parse_number(&mut Lexer::new(s));
+
+        assert_eq!(parse_str("0"), Ok(Value::Number(Number::Int(0))));
+        assert_eq!(parse_str("-42"), Ok(Value::Number(Number::Int(-42))));
+        assert_eq!(
+            parse_str("-1.5e10"),
+            Ok(Value::Number(Number::Float(-1.5e10)))
+        );
+        assert_eq!(parse_str("0.25"), Ok(Value::Number(Number::Float(0.25))));
+        assert_eq!(parse_str("2E-2"), Ok(Value::Number(Number::Float(0.02))));
+        assert_eq!(parse_str("1e+2"), Ok(Value::Number(Number::Float(100.0))));
+        assert_eq!(
+            parse_str("9223372036854775808"),
+            Ok(Value::Number(Number::UInt(9223372036854775808)))
+        );
+    }
+
+    #[test]
+    fn test_number_accessors() {
+        let int = Value::Number(Number::Int(-7));
+        assert_eq!(int.as_i64(), Some(-7));
+        assert_eq!(int.as_u64(), None);
+        assert_eq!(int.as_f64(), Some(-7.0));
+
+        let uint = Value::Number(Number::UInt(u64::MAX));
+        assert_eq!(uint.as_i64(), None);
+        assert_eq!(uint.as_u64(), Some(u64::MAX));
+        assert_eq!(uint.as_f64(), None);
+
+        let float = Value::Number(Number::Float(0.5));
+        assert_eq!(float.as_i64(), None);
+        assert_eq!(float.as_u64(), None);
+        assert_eq!(float.as_f64(), Some(0.5));
+
+        assert_eq!(Value::Number(Number::Int(i64::MAX)).as_f64(), None);
+        assert_eq!(Value::True.as_i64(), None);
+    }
+
+    #[test]
+    fn test_invalid_number() {
+        let parse_str = |s: &str| parse_number(&mut Lexer::new(s));
+
+        assert!(parse_str("01").is_err());
+        assert!(parse_str("-").is_err());
+        assert!(parse_str("1.").is_err());
+        assert!(parse_str(".5").is_err());
+        assert!(parse_str("1e").is_err());
+        assert!(parse_str("1e400").is_err());
+        assert!(parse_str("18446744073709551616").is_err());
+        assert!(parse_str("-9223372036854775809").is_err());
+    }
+}
@@ -0,0 +1,244 @@
+// json! dựng Value bằng cú pháp JSON ngay trong code Rust:
+//
+//     let name = "Dung";
+//     let value = json!({
+//         "title": "Rust",
+//         "year": 2023,
+//         "organizers": ["vbi", "techfest"],
+//         "presenter": { "name": name, "age": 20 + 7 },
+//     });
+//
+// giá trị không phải literal JSON là biểu thức Rust, đổi sang Value qua ToJson;
+// key là string literal hoặc (biểu thức) có .into() sang String
+#[macro_export]
+macro_rules! json {
+    ($($json:tt)+) => {
+        $crate::json_internal!($($json)+)
+    };
+}
+
+// tt-muncher: đọc từng token, gom phần tử của array / cặp key-value của object
+#[macro_export]
+#[doc(hidden)]
+macro_rules! json_internal {
+    // ---- array: @array [các phần tử đã đọc] phần còn lại ----
+
+    // hết input
+    (@array [$($elems:expr,)*]) => {
+        ::std::vec![$($elems,)*]
+    };
+    (@array [$($elems:expr),*]) => {
+        ::std::vec![$($elems),*]
+    };
+
+    // phần tử là literal JSON
+    (@array [$($elems:expr,)*] null $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(null)] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] true $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(true)] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] false $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!(false)] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!([$($array)*])] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] {$($map:tt)*} $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!({$($map)*})] $($rest)*)
+    };
+
+    // phần tử là biểu thức, theo sau là dấu phẩy hoặc là phần tử cuối
+    (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!($next),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] $last:expr) => {
+        $crate::json_internal!(@array [$($elems,)* $crate::json_internal!($last)])
+    };
+
+    // dấu phẩy sau một phần tử
+    (@array [$($elems:expr),*] , $($rest:tt)*) => {
+        $crate::json_internal!(@array [$($elems,)*] $($rest)*)
+    };
+
+    (@array [$($elems:expr),*] $unexpected:tt $($rest:tt)*) => {
+        $crate::json_unexpected!($unexpected)
+    };
+
+    // ---- object: @object map (key đang đọc) (phần còn lại) (bản sao để báo lỗi) ----
+
+    // hết input
+    (@object $object:ident () () ()) => {};
+
+    // đã có key và value, chèn vào rồi đọc tiếp
+    (@object $object:ident [$($key:tt)+] ($value:expr) , $($rest:tt)*) => {
+        let _ = $object.insert(::std::convert::Into::into($($key)+), $value);
+        $crate::json_internal!(@object $object () ($($rest)*) ($($rest)*));
+    };
+    (@object $object:ident [$($key:tt)+] ($value:expr) $unexpected:tt $($rest:tt)*) => {
+        $crate::json_unexpected!($unexpected);
+    };
+    (@object $object:ident [$($key:tt)+] ($value:expr)) => {
+        let _ = $object.insert(::std::convert::Into::into($($key)+), $value);
+    };
+
+    // value là literal JSON
+    (@object $object:ident ($($key:tt)+) (: null $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(null)) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: true $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(true)) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: false $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!(false)) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!([$($array)*])) $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: {$($map:tt)*} $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!({$($map)*})) $($rest)*);
+    };
+
+    // value là biểu thức
+    (@object $object:ident ($($key:tt)+) (: $value:expr , $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!($value)) , $($rest)*);
+    };
+    (@object $object:ident ($($key:tt)+) (: $value:expr) $copy:tt) => {
+        $crate::json_internal!(@object $object [$($key)+] ($crate::json_internal!($value)));
+    };
+
+    // thiếu value sau dấu hai chấm
+    (@object $object:ident ($($key:tt)+) (:) $copy:tt) => {
+        $crate::json_internal!();
+    };
+    // thiếu dấu hai chấm và value
+    (@object $object:ident ($($key:tt)+) () $copy:tt) => {
+        $crate::json_internal!();
+    };
+    (@object $object:ident () (: $($rest:tt)*) ($colon:tt $($copy:tt)*)) => {
+        $crate::json_unexpected!($colon);
+    };
+    (@object $object:ident ($($key:tt)*) (, $($rest:tt)*) ($comma:tt $($copy:tt)*)) => {
+        $crate::json_unexpected!($comma);
+    };
+
+    // key trong ngoặc tròn là một biểu thức
+    (@object $object:ident () (($key:expr) : $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object ($key) (: $($rest)*) (: $($rest)*));
+    };
+
+    // gom từng token của key cho tới dấu hai chấm
+    (@object $object:ident ($($key:tt)*) ($tt:tt $($rest:tt)*) $copy:tt) => {
+        $crate::json_internal!(@object $object ($($key)* $tt) ($($rest)*) ($($rest)*));
+    };
+
+    // ---- điểm vào ----
+
+    (null) => {
+        $crate::Value::Null
+    };
+    (true) => {
+        $crate::Value::True
+    };
+    (false) => {
+        $crate::Value::False
+    };
+    ([]) => {
+        $crate::Value::Array(::std::vec![])
+    };
+    ([ $($tt:tt)+ ]) => {
+        $crate::Value::Array($crate::json_internal!(@array [] $($tt)+))
+    };
+    ({}) => {
+        $crate::Value::Object($crate::Map::new())
+    };
+    ({ $($tt:tt)+ }) => {
+        $crate::Value::Object({
+            let mut object = $crate::Map::new();
+            $crate::json_internal!(@object object () ($($tt)+) ($($tt)+));
+            object
+        })
+    };
+
+    // còn lại là biểu thức Rust
+    ($other:expr) => {
+        $crate::ToJson::to_json(&$other)
+    };
+}
+
+// không có rule nào khớp, nên rustc báo lỗi đúng ngay token thừa
+#[macro_export]
+#[doc(hidden)]
+macro_rules! json_unexpected {
+    () => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, Map, Number, ToJson, Value};
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(json!(null), Value::Null);
+        assert_eq!(json!(true), Value::True);
+        assert_eq!(json!(false), Value::False);
+        assert_eq!(json!(101), Value::Number(Number::Int(101)));
+        assert_eq!(json!(-1.5), Value::Number(Number::Float(-1.5)));
+        assert_eq!(json!("value"), Value::String("value".to_string()));
+        assert_eq!(json!([]), Value::Array(vec![]));
+        assert_eq!(json!({}), Value::Object(Map::new()));
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let name = "Dung";
+        let tags = vec!["a", "b"];
+        let key = String::from("dynamic");
+        let missing: Option<u8> = None;
+        let inner = json!({ "x": 1 });
+
+        let value = json!({
+            "name": name,
+            "age": 20 + 7,
+            "tags": tags,
+            "first": tags[0],
+            (key): [inner, missing, { "nested": [null, false,], },],
+            "ok": name.len() == 4,
+        });
+        assert_eq!(
+            value,
+            parse(
+                r#"{"name": "Dung", "age": 27, "tags": ["a", "b"], "first": "a",
+                    "dynamic": [{"x": 1}, null, {"nested": [null, false]}], "ok": true}"#
+            )
+            .unwrap()
+        );
+        // biến vẫn dùng được, macro chỉ mượn
+        assert_eq!(json!(tags), tags.to_json());
+        assert_eq!(json!([name, 1u64, 2.5f32]).to_string(), r#"["Dung",1,2.5]"#);
+    }
+
+    #[test]
+    fn test_hygiene() {
+        // macro / trait cùng tên ở chỗ gọi không được ảnh hưởng tới json!
+        #[allow(unused_macros)]
+        macro_rules! vec {
+            ($($tt:tt)*) => {
+                compile_error!("the caller's vec! was used")
+            };
+        }
+        #[allow(dead_code)]
+        trait Into {}
+
+        let value = json!([1, [], [true, null], { "a": [], "b": [2] }]);
+        assert_eq!(value.to_string(), r#"[1,[],[true,null],{"a":[],"b":[2]}]"#);
+    }
+
+    #[test]
+    fn test_key_order() {
+        let value = json!({ "b": 1, "a": 2, "b": 3 });
+        let keys: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(keys, ["b", "a"]);
+        assert_eq!(value["b"], json!(3));
+    }
+}
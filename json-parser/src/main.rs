@@ -1,3 +1,4 @@
+#[allow(unused_variables, clippy::useless_vec)]
 fn main() {
     println!("Hello, world!");
     let input = "s";
@@ -57,6 +58,7 @@ pub trait People {
     fn weight(&self) -> String;
 }
 
+#[allow(dead_code)]
 struct Peter {}
 
 impl People for Peter {
@@ -69,6 +71,7 @@ impl People for Peter {
     }
 }
 
+#[allow(dead_code)]
 struct Alice {}
 
 impl People for Alice {
@@ -92,6 +95,7 @@ impl People for Alice {
 //     y: f64,
 // }
 // placeholder -> f32 hoặc f64
+#[allow(dead_code)]
 struct Point<T> {
     x: T,
     y: T,
@@ -101,6 +105,7 @@ struct Point<T> {
 // if let Ok ->
 // if let Err ->
 // function convert từ string sang u32
+#[allow(clippy::let_and_return)]
 fn convert_u32(input: &str) -> Option<u32> {
     let res = input.parse::<u32>().ok();
     res
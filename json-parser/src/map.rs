@@ -0,0 +1,368 @@
+// map giữ đúng thứ tự key như trong input, để ghi ra lại không bị đảo thứ tự
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::{slice, vec};
+
+#[derive(Clone)]
+pub struct Map<K, V> {
+    // theo thứ tự insert, None là entry đã bị remove (được dọn khi chiếm quá nửa)
+    entries: Vec<Option<Entry<K, V>>>,
+    len: usize,
+    // hash của key -> vị trí trong `entries`, key chỉ được lưu 1 lần ở `entries`
+    index: HashMap<u64, usize>,
+    hasher: RandomState,
+}
+
+#[derive(Clone)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    hash: u64,
+    // entry tiếp theo có cùng hash (rất hiếm khi có)
+    next: Option<usize>,
+}
+
+impl<K, V> Map<K, V> {
+    pub fn new() -> Self {
+        Map {
+            entries: Vec::new(),
+            len: 0,
+            index: HashMap::new(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // iterate in insertion order
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(self.entries.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut(self.entries.iter_mut())
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, v)| v)
+    }
+
+    fn entry(&self, i: usize) -> &Entry<K, V> {
+        self.entries[i]
+            .as_ref()
+            .expect("index points to a live entry")
+    }
+}
+
+impl<K: Hash + Eq, V> Map<K, V> {
+    // vị trí của key trong `entries`
+    fn find<Q>(&self, hash: u64, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mut i = *self.index.get(&hash)?;
+        loop {
+            let entry = self.entry(i);
+            if entry.key.borrow() == key {
+                return Some(i);
+            }
+            i = entry.next?;
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.find(self.hasher.hash_one(key), key)?;
+        Some(&self.entry(i).value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let i = self.find(self.hasher.hash_one(key), key)?;
+        self.entries[i].as_mut().map(|entry| &mut entry.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(self.hasher.hash_one(key), key).is_some()
+    }
+
+    // an existing key keeps its position, only the value is replaced
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let hash = self.hasher.hash_one(&key);
+        if let Some(i) = self.find(hash, &key) {
+            let entry = self.entries[i].as_mut()?;
+            return Some(std::mem::replace(&mut entry.value, value));
+        }
+        let next = self.index.insert(hash, self.entries.len());
+        self.entries.push(Some(Entry {
+            key,
+            value,
+            hash,
+            next,
+        }));
+        self.len += 1;
+        None
+    }
+
+    // the remaining keys keep their order
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        let i = self.find(hash, key)?;
+        let entry = self.entries[i].take()?;
+
+        // bỏ `i` khỏi chuỗi các entry cùng hash
+        let head = self.index[&hash];
+        if head == i {
+            match entry.next {
+                Some(next) => self.index.insert(hash, next),
+                None => self.index.remove(&hash),
+            };
+        } else {
+            let mut prev = head;
+            while let Some(next) = self.entry(prev).next.filter(|&next| next != i) {
+                prev = next;
+            }
+            if let Some(prev) = self.entries[prev].as_mut() {
+                prev.next = entry.next;
+            }
+        }
+
+        self.len -= 1;
+        if self.len < self.entries.len() / 2 {
+            self.compact();
+        }
+        Some(entry.value)
+    }
+
+    // bỏ các entry đã remove và đánh lại vị trí
+    fn compact(&mut self) {
+        self.entries.retain(Option::is_some);
+        self.index.clear();
+        for (i, entry) in self.entries.iter_mut().enumerate() {
+            if let Some(entry) = entry {
+                entry.next = self.index.insert(entry.hash, i);
+            }
+        }
+    }
+}
+
+impl<K, V> Default for Map<K, V> {
+    fn default() -> Self {
+        Map::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Map<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// hai object bằng nhau khi có cùng các cặp key-value, không quan tâm thứ tự
+impl<K: Hash + Eq, V: PartialEq> PartialEq for Map<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for Map<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for Map<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+pub struct Iter<'a, K, V>(slice::Iter<'a, Option<Entry<K, V>>>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.0.by_ref().flatten().next()?;
+        Some((&entry.key, &entry.value))
+    }
+}
+
+pub struct IterMut<'a, K, V>(slice::IterMut<'a, Option<Entry<K, V>>>);
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.0.by_ref().flatten().next()?;
+        Some((&entry.key, &mut entry.value))
+    }
+}
+
+pub struct IntoIter<K, V>(vec::IntoIter<Option<Entry<K, V>>>);
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.0.by_ref().flatten().next()?;
+        Some((entry.key, entry.value))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a Map<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut Map<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<K, V> IntoIterator for Map<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.entries.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insertion_order() {
+        let mut map = Map::new();
+        map.insert("b", 1);
+        map.insert("a", 2);
+        map.insert("c", 3);
+        assert_eq!(map.insert("a", 20), Some(2));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["b", "a", "c"]);
+
+        assert_eq!(map.remove("b"), Some(1));
+        assert_eq!(map.remove("b"), None);
+        map.insert("b", 4);
+        assert_eq!(
+            map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            [("a", 20), ("c", 3), ("b", 4)]
+        );
+        assert_eq!(map.get("c"), Some(&3));
+        *map.get_mut("c").unwrap() = 30;
+        assert_eq!(map.get("c"), Some(&30));
+        assert_eq!(map.len(), 3);
+    }
+
+    #[test]
+    fn test_remove_keeps_order() {
+        let mut map: Map<String, usize> = (0..100).map(|i| (i.to_string(), i)).collect();
+        for i in (0..100).filter(|i| i % 3 != 0) {
+            assert_eq!(map.remove(&i.to_string()), Some(i));
+        }
+        assert_eq!(map.len(), 34);
+        let kept: Vec<usize> = (0..100).filter(|i| i % 3 == 0).collect();
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), kept);
+        for i in 0..100 {
+            let expected = (i % 3 == 0).then_some(&i);
+            assert_eq!(map.get(i.to_string().as_str()), expected);
+        }
+
+        map.insert("1".to_string(), 1);
+        *map.get_mut("0").unwrap() = 10;
+        assert_eq!(map.remove("99"), Some(99));
+        let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+        assert_eq!(keys.first(), Some(&"0"));
+        assert_eq!(keys.last(), Some(&"1"));
+        assert_eq!(map.len(), 34);
+        assert_eq!(
+            map.clone().into_iter().collect::<Vec<_>>(),
+            map.iter().map(|(k, v)| (k.clone(), *v)).collect::<Vec<_>>()
+        );
+    }
+
+    // mọi key cùng 1 hash, để đi qua chuỗi entry trùng hash
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Colliding(u8);
+
+    impl Hash for Colliding {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            state.write_u8(0);
+        }
+    }
+
+    #[test]
+    fn test_hash_collisions() {
+        let mut map: Map<_, _> = (0..10).map(|i| (Colliding(i), i)).collect();
+        for i in [9, 0, 4, 5, 6, 7] {
+            assert_eq!(map.remove(&Colliding(i)), Some(i));
+            assert_eq!(map.remove(&Colliding(i)), None);
+        }
+        assert_eq!(map.insert(Colliding(2), 20), Some(2));
+        assert_eq!(map.insert(Colliding(0), 0), None);
+        assert_eq!(
+            map.iter().map(|(k, v)| (k.0, *v)).collect::<Vec<_>>(),
+            [(1, 1), (2, 20), (3, 3), (8, 8), (0, 0)]
+        );
+        for i in 0..10 {
+            assert_eq!(
+                map.contains_key(&Colliding(i)),
+                [0, 1, 2, 3, 8].contains(&i)
+            );
+        }
+    }
+
+    #[test]
+    fn test_eq_ignores_order() {
+        let a: Map<_, _> = [("x", 1), ("y", 2)].into_iter().collect();
+        let b: Map<_, _> = [("y", 2), ("x", 1)].into_iter().collect();
+        let c: Map<_, _> = [("y", 2), ("x", 3)].into_iter().collect();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
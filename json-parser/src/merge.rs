@@ -0,0 +1,130 @@
+// JSON Merge Patch (RFC 7386): patch có cùng hình dạng với document,
+// object được merge đệ quy, `null` nghĩa là xoá key
+use crate::{Map, Value};
+
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !matches!(target, Value::Object(_)) {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(object) = target else {
+        unreachable!()
+    };
+
+    for (key, value) in patch.iter() {
+        if *value == Value::Null {
+            object.remove(key);
+            continue;
+        }
+        if !object.contains_key(key) {
+            object.insert(key.clone(), Value::Null);
+        }
+        if let Some(child) = object.get_mut(key) {
+            merge_patch(child, value);
+        }
+    }
+}
+
+// merge patch biến `from` thành `to`.
+// `null` nằm trong object của `to` không biểu diễn được (nó sẽ xoá key)
+pub fn create_merge_patch(from: &Value, to: &Value) -> Value {
+    let (Value::Object(from), Value::Object(to)) = (from, to) else {
+        return to.clone();
+    };
+
+    let mut patch = Map::new();
+    for key in from.keys().filter(|key| !to.contains_key(*key)) {
+        patch.insert(key.clone(), Value::Null);
+    }
+    for (key, new) in to.iter() {
+        match from.get(key) {
+            Some(old) if old == new => {}
+            Some(old) => {
+                patch.insert(key.clone(), create_merge_patch(old, new));
+            }
+            None => {
+                patch.insert(key.clone(), new.clone());
+            }
+        }
+    }
+    Value::Object(patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    // RFC 7386, appendix A
+    #[test]
+    fn test_rfc_examples() {
+        let cases = [
+            (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"a":null}"#, "{}"),
+            (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+            (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+            (
+                r#"{"a":{"b":"c"}}"#,
+                r#"{"a":{"b":"d","c":null}}"#,
+                r#"{"a":{"b":"d"}}"#,
+            ),
+            (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+            (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+            (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+            (r#"{"a":"foo"}"#, "null", "null"),
+            (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+            (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+            ("[1,2]", r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+            ("{}", r#"{"a":{"bb":{"ccc":null}}}"#, r#"{"a":{"bb":{}}}"#),
+        ];
+        for (target, patch, expected) in cases {
+            let mut doc = parse(target).unwrap();
+            merge_patch(&mut doc, &parse(patch).unwrap());
+            assert_eq!(doc.to_string(), expected, "{} + {}", target, patch);
+        }
+    }
+
+    #[test]
+    fn test_layered_config() {
+        let mut config =
+            parse(r#"{"port": 80, "log": {"level": "info", "file": "app.log"}}"#).unwrap();
+        let layers = [
+            r#"{"log": {"level": "debug"}}"#,
+            r#"{"port": 8080, "log": {"file": null}, "tls": true}"#,
+        ];
+        for layer in layers {
+            merge_patch(&mut config, &parse(layer).unwrap());
+        }
+        assert_eq!(
+            config.to_string(),
+            r#"{"port":8080,"log":{"level":"debug"},"tls":true}"#
+        );
+    }
+
+    #[test]
+    fn test_create_merge_patch() {
+        let cases = [
+            (r#"{"a": 1, "b": 2}"#, r#"{"a": 1, "b": 2}"#, "{}"),
+            (
+                r#"{"a": 1, "b": {"c": 2, "d": 3}, "e": [1]}"#,
+                r#"{"a": 1, "b": {"c": 4}, "e": [1, 2], "f": "x"}"#,
+                r#"{"b":{"d":null,"c":4},"e":[1,2],"f":"x"}"#,
+            ),
+            (r#"{"a": 1}"#, "[1]", "[1]"),
+            ("[1]", r#"{"a": {"b": 1}}"#, r#"{"a":{"b":1}}"#),
+        ];
+        for (from, to, expected) in cases {
+            let (mut doc, to) = (parse(from).unwrap(), parse(to).unwrap());
+            let patch = create_merge_patch(&doc, &to);
+            assert_eq!(patch.to_string(), expected);
+
+            merge_patch(&mut doc, &patch);
+            assert_eq!(doc, to);
+        }
+    }
+}
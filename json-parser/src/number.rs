@@ -0,0 +1,75 @@
+// JSON has a single number type, but we keep track of what kind of
+// number was written so counters and ratios don't lose precision
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Number {
+    // signed integer that fits in i64
+    Int(i64),
+    // positive integer above i64::MAX
+    UInt(u64),
+    // number with a fraction or an exponent
+    Float(f64),
+}
+
+impl Number {
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Number::Int(n) => Some(n),
+            Number::UInt(n) => i64::try_from(n).ok(),
+            Number::Float(_) => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Number::Int(n) => u64::try_from(n).ok(),
+            Number::UInt(n) => Some(n),
+            Number::Float(_) => None,
+        }
+    }
+
+    // integers only convert when f64 can hold them exactly,
+    // `f64::from_json` follows the same rule and errors instead of rounding
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Number::Int(n) => {
+                let f = n as f64;
+                (f as i128 == n as i128).then_some(f)
+            }
+            Number::UInt(n) => {
+                let f = n as f64;
+                (f as i128 == n as i128).then_some(f)
+            }
+            Number::Float(f) => Some(f),
+        }
+    }
+
+    // is_i64 / is_u64 hỏi giá trị có đọc được bằng as_i64 / as_u64 không,
+    // không phụ thuộc variant: Int(5) và UInt(5) đều là cả i64 lẫn u64
+    pub fn is_i64(&self) -> bool {
+        self.as_i64().is_some()
+    }
+
+    pub fn is_u64(&self) -> bool {
+        self.as_u64().is_some()
+    }
+
+    // còn is_f64 theo variant: chỉ số có phần thập phân hoặc số mũ
+    pub fn is_f64(&self) -> bool {
+        matches!(self, Number::Float(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_predicates() {
+        for n in [Number::Int(5), Number::UInt(5)] {
+            assert!(n.is_i64() && n.is_u64() && !n.is_f64());
+        }
+        assert!(Number::Int(-5).is_i64() && !Number::Int(-5).is_u64());
+        assert!(Number::UInt(u64::MAX).is_u64() && !Number::UInt(u64::MAX).is_i64());
+        assert!(Number::Float(5.0).is_f64() && !Number::Float(5.0).is_i64());
+    }
+}
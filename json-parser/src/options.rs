@@ -0,0 +1,54 @@
+// các tuỳ chọn cho parser, mặc định là RFC 8259
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    // chỉ chấp nhận object ở top level (hành vi cũ của `parse`)
+    pub object_only: bool,
+    // what to do when an object has the same key more than once
+    pub duplicate_keys: DuplicateKeys,
+
+    // cú pháp nới lỏng (JSON5), tắt hết theo mặc định, bật riêng từng cái:
+    // `// ...` và `/* ... */` ở những chỗ được có whitespace
+    pub comments: bool,
+    // `[1, 2,]`, `{"a": 1,}`
+    pub trailing_commas: bool,
+    // 'string', trong đó `\'` là escape hợp lệ
+    pub single_quotes: bool,
+    // key là identifier không có ngoặc kép: `{name: "Dung"}`
+    pub unquoted_keys: bool,
+    // 0x1F, -0xff
+    pub hex_numbers: bool,
+    // +1, +1.5
+    pub leading_plus: bool,
+    // Infinity, -Infinity, NaN; được đọc thành Number::Float, nhưng JSON không có
+    // các giá trị này nên to_string / to_string_pretty ghi chúng ra là `null`:
+    // parse rồi ghi lại sẽ mất giá trị
+    pub infinity_nan: bool,
+}
+
+impl ParseOptions {
+    // bật mọi phần mở rộng ở trên, cho file config viết tay
+    pub fn relaxed() -> Self {
+        ParseOptions {
+            comments: true,
+            trailing_commas: true,
+            single_quotes: true,
+            unquoted_keys: true,
+            hex_numbers: true,
+            leading_plus: true,
+            infinity_nan: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeys {
+    // báo lỗi, chỉ ra vị trí của cả 2 key
+    Reject,
+    KeepFirst,
+    #[default]
+    KeepLast,
+    // value của mọi key đều thành 1 `Value::Array` theo thứ tự xuất hiện, kể cả key
+    // chỉ có 1 lần, để `{"a": 1, "a": 3}` khác với `{"a": [1, 3]}`
+    Collect,
+}
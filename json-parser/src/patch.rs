@@ -0,0 +1,782 @@
+// JSON Patch (RFC 6902): danh sách operation add/remove/replace/move/copy/test,
+// mỗi operation trỏ tới value bằng JSON Pointer
+use std::error::Error;
+use std::fmt;
+
+use crate::path::values_equal;
+use crate::pointer::{array_index, escape, parse_pointer, to_pointer, walk_mut};
+use crate::{Map, PointerError, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Patch(pub Vec<Operation>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchError {
+    // patch document không đúng định dạng
+    Invalid(String),
+    // operation thứ `index` trỏ tới value không hợp lệ
+    Pointer { index: usize, error: PointerError },
+    // `test` không khớp
+    TestFailed { index: usize, path: String },
+    // không thể move 1 value vào chính con của nó
+    MoveIntoChild { index: usize, from: String },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::Invalid(msg) => write!(f, "invalid patch: {}", msg),
+            PatchError::Pointer { index, error } => write!(f, "operation {}: {}", index, error),
+            PatchError::TestFailed { index, path } => {
+                write!(f, "operation {}: test failed at `{}`", index, path)
+            }
+            PatchError::MoveIntoChild { index, from } => {
+                write!(
+                    f,
+                    "operation {}: can not move `{}` into itself",
+                    index, from
+                )
+            }
+        }
+    }
+}
+
+impl Error for PatchError {}
+
+// áp dụng patch dạng JSON, document giữ nguyên nếu có operation lỗi
+pub fn apply_patch(doc: &mut Value, patch: &Value) -> Result<(), PatchError> {
+    Patch::from_value(patch)?.apply(doc)
+}
+
+// patch biến `from` thành `to`; ở mỗi object / array, nếu các op con dài hơn
+// 1 op replace cả value thì dùng replace
+pub fn diff(from: &Value, to: &Value) -> Patch {
+    let mut ops = vec![];
+    diff_values(from, to, &mut String::new(), &mut ops);
+    Patch(ops)
+}
+
+impl Patch {
+    pub fn from_value(value: &Value) -> Result<Patch, PatchError> {
+        let Value::Array(items) = value else {
+            return Err(PatchError::Invalid(
+                "expected an array of operations".to_string(),
+            ));
+        };
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| Operation::from_value(item, i))
+            .collect::<Result<_, _>>()
+            .map(Patch)
+    }
+
+    pub fn to_value(&self) -> Value {
+        Value::Array(self.0.iter().map(Operation::to_value).collect())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn apply(&self, doc: &mut Value) -> Result<(), PatchError> {
+        // chạy trên bản sao để lỗi giữa chừng không để lại patch dở dang
+        let mut res = doc.clone();
+        for (index, op) in self.0.iter().enumerate() {
+            op.apply(&mut res, index)?;
+        }
+        *doc = res;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_value())
+    }
+}
+
+impl Operation {
+    fn from_value(value: &Value, index: usize) -> Result<Operation, PatchError> {
+        let invalid = |msg: &str| PatchError::Invalid(format!("operation {}: {}", index, msg));
+        let Value::Object(object) = value else {
+            return Err(invalid("expected an object"));
+        };
+        let string = |key: &str| match object.get(key) {
+            Some(Value::String(s)) => Ok(s.clone()),
+            Some(_) => Err(invalid(&format!("`{}` must be a string", key))),
+            None => Err(invalid(&format!("missing `{}`", key))),
+        };
+        let value = || {
+            object
+                .get("value")
+                .cloned()
+                .ok_or_else(|| invalid("missing `value`"))
+        };
+
+        let path = string("path")?;
+        Ok(match string("op")?.as_str() {
+            "add" => Operation::Add {
+                path,
+                value: value()?,
+            },
+            "remove" => Operation::Remove { path },
+            "replace" => Operation::Replace {
+                path,
+                value: value()?,
+            },
+            "move" => Operation::Move {
+                from: string("from")?,
+                path,
+            },
+            "copy" => Operation::Copy {
+                from: string("from")?,
+                path,
+            },
+            "test" => Operation::Test {
+                path,
+                value: value()?,
+            },
+            op => return Err(invalid(&format!("unknown op `{}`", op))),
+        })
+    }
+
+    pub fn to_value(&self) -> Value {
+        let (op, path, from, value) = match self {
+            Operation::Add { path, value } => ("add", path, None, Some(value)),
+            Operation::Remove { path } => ("remove", path, None, None),
+            Operation::Replace { path, value } => ("replace", path, None, Some(value)),
+            Operation::Move { from, path } => ("move", path, Some(from), None),
+            Operation::Copy { from, path } => ("copy", path, Some(from), None),
+            Operation::Test { path, value } => ("test", path, None, Some(value)),
+        };
+        let mut object = Map::new();
+        object.insert("op".to_string(), Value::String(op.to_string()));
+        object.insert("path".to_string(), Value::String(path.clone()));
+        if let Some(from) = from {
+            object.insert("from".to_string(), Value::String(from.clone()));
+        }
+        if let Some(value) = value {
+            object.insert("value".to_string(), value.clone());
+        }
+        Value::Object(object)
+    }
+
+    fn apply(&self, doc: &mut Value, index: usize) -> Result<(), PatchError> {
+        let pointer_error = |error| PatchError::Pointer { index, error };
+        let not_found = |path: &str| pointer_error(PointerError::NotFound(path.to_string()));
+
+        match self {
+            Operation::Add { path, value } => add(doc, path, value.clone()).map_err(pointer_error),
+            Operation::Remove { path } => doc.remove_pointer(path).map(drop).map_err(pointer_error),
+            Operation::Replace { path, value } => {
+                parse_pointer(path).map_err(pointer_error)?;
+                let target = doc.pointer_mut(path).ok_or_else(|| not_found(path))?;
+                *target = value.clone();
+                Ok(())
+            }
+            Operation::Move { from, path } => {
+                if from == path {
+                    return doc.pointer(from).map(drop).ok_or_else(|| not_found(from));
+                }
+                if path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+                    return Err(PatchError::MoveIntoChild {
+                        index,
+                        from: from.clone(),
+                    });
+                }
+                let value = doc.remove_pointer(from).map_err(pointer_error)?;
+                add(doc, path, value).map_err(pointer_error)
+            }
+            Operation::Copy { from, path } => {
+                parse_pointer(from).map_err(pointer_error)?;
+                let value = doc.pointer(from).cloned().ok_or_else(|| not_found(from))?;
+                add(doc, path, value).map_err(pointer_error)
+            }
+            Operation::Test { path, value } => {
+                parse_pointer(path).map_err(pointer_error)?;
+                match doc.pointer(path) {
+                    Some(actual) if values_equal(actual, value) => Ok(()),
+                    _ => Err(PatchError::TestFailed {
+                        index,
+                        path: path.clone(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+// khác `set_pointer` ở chỗ array thì chèn vào chứ không thay phần tử cũ
+fn add(doc: &mut Value, path: &str, value: Value) -> Result<(), PointerError> {
+    let mut tokens = parse_pointer(path)?;
+    let Some(last) = tokens.pop() else {
+        *doc = value;
+        return Ok(());
+    };
+
+    match walk_mut(doc, &tokens)? {
+        Value::Object(object) => {
+            object.insert(last, value);
+        }
+        Value::Array(items) => match array_index(&last) {
+            Some(i) if i <= items.len() => items.insert(i, value),
+            None if last == "-" => items.push(value),
+            _ => return Err(PointerError::InvalidIndex(path.to_string())),
+        },
+        _ => return Err(PointerError::NotAContainer(to_pointer(&tokens))),
+    }
+    Ok(())
+}
+
+fn diff_values(from: &Value, to: &Value, path: &mut String, ops: &mut Vec<Operation>) {
+    if from == to {
+        return;
+    }
+    match (from, to) {
+        (Value::Object(a), Value::Object(b)) => {
+            let start = ops.len();
+            for (key, old) in a.iter() {
+                let len = push_token(path, key);
+                match b.get(key) {
+                    Some(new) => diff_values(old, new, path, ops),
+                    None => ops.push(Operation::Remove { path: path.clone() }),
+                }
+                path.truncate(len);
+            }
+            for (key, new) in b.iter().filter(|(key, _)| !a.contains_key(*key)) {
+                let len = push_token(path, key);
+                ops.push(Operation::Add {
+                    path: path.clone(),
+                    value: new.clone(),
+                });
+                path.truncate(len);
+            }
+            replace_if_shorter(to, path, start, ops);
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            let start = ops.len();
+            diff_arrays(a, b, path, ops);
+            replace_if_shorter(to, path, start, ops);
+        }
+        _ => ops.push(Operation::Replace {
+            path: path.clone(),
+            value: to.clone(),
+        }),
+    }
+}
+
+// các op từ `start` trở đi dài hơn 1 op replace cả value thì dùng replace
+fn replace_if_shorter(to: &Value, path: &str, start: usize, ops: &mut Vec<Operation>) {
+    let replace = Operation::Replace {
+        path: path.to_string(),
+        value: to.clone(),
+    };
+    if encoded_len(&ops[start..]) > encoded_len(std::slice::from_ref(&replace)) {
+        ops.truncate(start);
+        ops.push(replace);
+    }
+}
+
+// độ dài của các op khi serialize, kể cả dấu `,` giữa chúng
+fn encoded_len(ops: &[Operation]) -> usize {
+    ops.iter()
+        .map(|op| op.to_value().to_string().len() + 1)
+        .sum()
+}
+
+fn diff_arrays(a: &[Value], b: &[Value], path: &mut String, ops: &mut Vec<Operation>) {
+    // index trong array đang được sửa
+    let mut index = 0;
+    for step in align(a, b) {
+        let len = push_token(path, &index.to_string());
+        match step {
+            Step::Same(..) => index += 1,
+            Step::Change(i, j) => {
+                diff_values(&a[i], &b[j], path, ops);
+                index += 1;
+            }
+            Step::Remove(_) => ops.push(Operation::Remove { path: path.clone() }),
+            Step::Insert(j) => {
+                ops.push(Operation::Add {
+                    path: path.clone(),
+                    value: b[j].clone(),
+                });
+                index += 1;
+            }
+        }
+        path.truncate(len);
+    }
+}
+
+// 1 bước để biến array `a` thành `b`, index là vị trí trong `a` / `b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Step {
+    Same(usize, usize),
+    Change(usize, usize),
+    Remove(usize),
+    Insert(usize),
+}
+
+// Myers chỉ tốn O((n + m) * D) với D là số phần tử chèn/xoá; quá mức này thì
+// ghép các phần tử theo vị trí, diff_values sẽ thay cả array nếu như vậy ngắn hơn
+const MAX_ALIGN_COST: usize = 1 << 24;
+
+// ít phần tử chèn/xoá nhất (Myers, bộ nhớ tuyến tính),
+// cặp xoá + chèn liền nhau thì gộp thành Change
+pub(crate) fn align(a: &[Value], b: &[Value]) -> Vec<Step> {
+    let mut edits = vec![];
+    let limit = MAX_ALIGN_COST / (a.len() + b.len() + 1);
+    if !myers(a, b, (0, 0), limit, &mut edits) {
+        edits.clear();
+        let common = a.len().min(b.len());
+        edits.extend((0..common).map(|i| Step::Change(i, i)));
+        edits.extend((common..a.len()).map(Step::Remove));
+        edits.extend((common..b.len()).map(Step::Insert));
+        return edits;
+    }
+
+    let mut steps = Vec::with_capacity(edits.len());
+    let mut rest = edits.as_slice();
+    while let Some(&first) = rest.first() {
+        if let Step::Same(..) = first {
+            steps.push(first);
+            rest = &rest[1..];
+            continue;
+        }
+        // 1 đoạn chỉ có Remove và Insert, ghép từng cặp thành Change
+        let len = rest
+            .iter()
+            .take_while(|step| !matches!(step, Step::Same(..)))
+            .count();
+        let (removed, inserted): (Vec<Step>, Vec<Step>) = rest[..len]
+            .iter()
+            .partition(|step| matches!(step, Step::Remove(_)));
+        for k in 0..removed.len().max(inserted.len()) {
+            steps.push(match (removed.get(k), inserted.get(k)) {
+                (Some(&Step::Remove(i)), Some(&Step::Insert(j))) => Step::Change(i, j),
+                (Some(&step), None) | (None, Some(&step)) => step,
+                _ => unreachable!(),
+            });
+        }
+        rest = &rest[len..];
+    }
+    steps
+}
+
+// ghi Same/Remove/Insert của a -> b vào `edits`, `start` là vị trí của a[0], b[0]
+// trong array gốc; trả về false nếu cần nhiều hơn `limit` bước ở mỗi chiều
+fn myers(
+    a: &[Value],
+    b: &[Value],
+    start: (usize, usize),
+    limit: usize,
+    edits: &mut Vec<Step>,
+) -> bool {
+    // bỏ phần đầu và phần cuối giống nhau
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    let (x0, y0) = (start.0 + prefix, start.1 + prefix);
+
+    let head = edits.len();
+    edits.extend((0..prefix).map(|k| Step::Same(start.0 + k, start.1 + k)));
+    if a.is_empty() || b.is_empty() {
+        edits.extend((0..a.len()).map(|i| Step::Remove(x0 + i)));
+        edits.extend((0..b.len()).map(|j| Step::Insert(y0 + j)));
+    } else {
+        let Some((x, y, u, v)) = middle_snake(a, b, limit) else {
+            edits.truncate(head);
+            return false;
+        };
+        // 2 nửa đều có ít bước hơn nên không cần giới hạn nữa
+        myers(&a[..x], &b[..y], (x0, y0), usize::MAX, edits);
+        edits.extend((0..u - x).map(|k| Step::Same(x0 + x + k, y0 + y + k)));
+        myers(&a[u..], &b[v..], (x0 + u, y0 + v), usize::MAX, edits);
+    }
+    let (n, m) = (start.0 + prefix + a.len(), start.1 + prefix + b.len());
+    edits.extend((0..suffix).map(|k| Step::Same(n + k, m + k)));
+    true
+}
+
+// đoạn chéo (x, y) -> (u, v) nằm giữa 1 đường đi ngắn nhất,
+// tìm từ 2 đầu cùng lúc để chỉ cần O(n + m) bộ nhớ
+fn middle_snake(a: &[Value], b: &[Value], limit: usize) -> Option<(usize, usize, usize, usize)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = (n + m + 1) / 2;
+    // forward[k]: x xa nhất trên đường chéo k = x - y,
+    // backward[k]: như vậy nhưng đếm từ cuối, đường chéo k ứng với delta - k
+    let offset = max + 1;
+    let mut forward = vec![0; 2 * offset as usize + 1];
+    let mut backward = vec![0; 2 * offset as usize + 1];
+    let at = |k: isize| (k + offset) as usize;
+
+    for d in 0..=max {
+        if d as usize > limit {
+            return None;
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && forward[at(k - 1)] < forward[at(k + 1)]) {
+                forward[at(k + 1)]
+            } else {
+                forward[at(k - 1)] + 1
+            };
+            let (sx, sy) = (x, x - k);
+            while x < n && x - k < m && a[x as usize] == b[(x - k) as usize] {
+                x += 1;
+            }
+            forward[at(k)] = x;
+            if odd && (delta - k).abs() < d && x + backward[at(delta - k)] >= n {
+                return Some((sx as usize, sy as usize, x as usize, (x - k) as usize));
+            }
+        }
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && backward[at(k - 1)] < backward[at(k + 1)]) {
+                backward[at(k + 1)]
+            } else {
+                backward[at(k - 1)] + 1
+            };
+            let (ex, ey) = (x, x - k);
+            while x < n && x - k < m && a[(n - 1 - x) as usize] == b[(m - 1 - (x - k)) as usize] {
+                x += 1;
+            }
+            backward[at(k)] = x;
+            if !odd && (delta - k).abs() <= d && forward[at(delta - k)] + x >= n {
+                return Some((
+                    (n - x) as usize,
+                    (m - (x - k)) as usize,
+                    (n - ex) as usize,
+                    (m - ey) as usize,
+                ));
+            }
+        }
+    }
+    None
+}
+
+// thêm `/token` vào path, trả về độ dài cũ để cắt lại
+fn push_token(path: &mut String, token: &str) -> usize {
+    let len = path.len();
+    path.push('/');
+    path.push_str(&escape(token));
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn patched(doc: &str, patch: &str) -> Result<String, PatchError> {
+        let mut doc = parse(doc).unwrap();
+        apply_patch(&mut doc, &parse(patch).unwrap())?;
+        Ok(doc.to_string())
+    }
+
+    // các ví dụ trong RFC 6902, appendix A
+    #[test]
+    fn test_rfc_examples() {
+        let cases = [
+            (
+                r#"{"foo": "bar"}"#,
+                r#"[{"op": "add", "path": "/baz", "value": "qux"}]"#,
+                r#"{"foo":"bar","baz":"qux"}"#,
+            ),
+            (
+                r#"{"foo": ["bar", "baz"]}"#,
+                r#"[{"op": "add", "path": "/foo/1", "value": "qux"}]"#,
+                r#"{"foo":["bar","qux","baz"]}"#,
+            ),
+            (
+                r#"{"baz": "qux", "foo": "bar"}"#,
+                r#"[{"op": "remove", "path": "/baz"}]"#,
+                r#"{"foo":"bar"}"#,
+            ),
+            (
+                r#"{"foo": ["bar", "qux", "baz"]}"#,
+                r#"[{"op": "remove", "path": "/foo/1"}]"#,
+                r#"{"foo":["bar","baz"]}"#,
+            ),
+            (
+                r#"{"baz": "qux", "foo": "bar"}"#,
+                r#"[{"op": "replace", "path": "/baz", "value": "boo"}]"#,
+                r#"{"baz":"boo","foo":"bar"}"#,
+            ),
+            (
+                r#"{"foo": {"bar": "baz", "waldo": "fred"}, "qux": {"corge": "grault"}}"#,
+                r#"[{"op": "move", "from": "/foo/waldo", "path": "/qux/thud"}]"#,
+                r#"{"foo":{"bar":"baz"},"qux":{"corge":"grault","thud":"fred"}}"#,
+            ),
+            (
+                r#"{"foo": ["all", "grass", "cows", "eat"]}"#,
+                r#"[{"op": "move", "from": "/foo/1", "path": "/foo/3"}]"#,
+                r#"{"foo":["all","cows","eat","grass"]}"#,
+            ),
+            (
+                r#"{"baz": "qux", "foo": ["a", 2, "c"]}"#,
+                r#"[{"op": "test", "path": "/baz", "value": "qux"},
+                    {"op": "test", "path": "/foo/1", "value": 2.0}]"#,
+                r#"{"baz":"qux","foo":["a",2,"c"]}"#,
+            ),
+            (
+                r#"{"foo": "bar"}"#,
+                r#"[{"op": "add", "path": "/child", "value": {"grandchild": {}}}]"#,
+                r#"{"foo":"bar","child":{"grandchild":{}}}"#,
+            ),
+            (
+                r#"{"foo": ["bar"]}"#,
+                r#"[{"op": "add", "path": "/foo/-", "value": ["abc", "def"]}]"#,
+                r#"{"foo":["bar",["abc","def"]]}"#,
+            ),
+            (
+                r#"{"foo": "bar"}"#,
+                r#"[{"op": "copy", "from": "/foo", "path": "/baz"}]"#,
+                r#"{"foo":"bar","baz":"bar"}"#,
+            ),
+            (
+                r#"{"foo": 1}"#,
+                r#"[{"op": "add", "path": "", "value": [1]}]"#,
+                "[1]",
+            ),
+        ];
+        for (doc, patch, expected) in cases {
+            assert_eq!(patched(doc, patch).unwrap(), expected, "{}", patch);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            patched(
+                r#"{"baz": "qux"}"#,
+                r#"[{"op": "test", "path": "/baz", "value": "bar"}]"#
+            ),
+            Err(PatchError::TestFailed {
+                index: 0,
+                path: "/baz".to_string()
+            })
+        );
+        assert_eq!(
+            patched(
+                r#"{"foo": "bar"}"#,
+                r#"[{"op": "add", "path": "/baz/bat", "value": "qux"}]"#
+            ),
+            Err(PatchError::Pointer {
+                index: 0,
+                error: PointerError::NotFound("/baz".to_string())
+            })
+        );
+        assert_eq!(
+            patched(
+                r#"{"a": {"b": 1}}"#,
+                r#"[{"op": "move", "from": "/a", "path": "/a/b/c"}]"#
+            ),
+            Err(PatchError::MoveIntoChild {
+                index: 0,
+                from: "/a".to_string()
+            })
+        );
+        assert_eq!(
+            patched("[1]", r#"[{"op": "replace", "path": "/1", "value": 2}]"#),
+            Err(PatchError::Pointer {
+                index: 0,
+                error: PointerError::NotFound("/1".to_string())
+            })
+        );
+        for patch in [
+            r#"{"op": "add"}"#,
+            r#"[{"path": "/a"}]"#,
+            r#"[{"op": "add", "path": "/a"}]"#,
+            r#"[{"op": "move", "path": "/a"}]"#,
+            r#"[{"op": "jump", "path": "/a"}]"#,
+            r#"[{"op": "remove", "path": 1}]"#,
+        ] {
+            assert!(
+                matches!(patched("{}", patch), Err(PatchError::Invalid(_))),
+                "{}",
+                patch
+            );
+        }
+    }
+
+    #[test]
+    fn test_atomic() {
+        let mut doc = parse(r#"{"a": 1, "b": [1, 2]}"#).unwrap();
+        let before = doc.clone();
+        let patch = parse(
+            r#"[{"op": "add", "path": "/c", "value": 3},
+                {"op": "remove", "path": "/b/0"},
+                {"op": "test", "path": "/a", "value": 2}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            apply_patch(&mut doc, &patch),
+            Err(PatchError::TestFailed {
+                index: 2,
+                path: "/a".to_string()
+            })
+        );
+        assert_eq!(doc, before);
+    }
+
+    #[test]
+    fn test_diff() {
+        let cases = [
+            (r#"{"a": 1}"#, r#"{"a": 1}"#, "[]"),
+            (
+                r#"{"a": 1, "b": {"c": [1, 2]}, "d/e": true, "g": ["kept", "as", "it", "is"], "h": "so replacing the whole object is longer than four small ops"}"#,
+                r#"{"a": 2, "b": {"c": [1, 2, 3]}, "f": null, "g": ["kept", "as", "it", "is"], "h": "so replacing the whole object is longer than four small ops"}"#,
+                r#"[{"op":"replace","path":"/a","value":2},{"op":"add","path":"/b/c/2","value":3},{"op":"remove","path":"/d~1e"},{"op":"add","path":"/f","value":null}]"#,
+            ),
+            (
+                r#"["one", "two", "three", "four", "five"]"#,
+                r#"["zero", "one", "two", "four", "five"]"#,
+                r#"[{"op":"add","path":"/0","value":"zero"},{"op":"remove","path":"/3"}]"#,
+            ),
+            (
+                r#"["one", "two", "three", "four", "five", "six"]"#,
+                r#"["one", "four", "five", "six"]"#,
+                r#"[{"op":"remove","path":"/1"},{"op":"remove","path":"/1"}]"#,
+            ),
+            (
+                r#"["one", "two", "three"]"#,
+                r#"["one", "nine", "three"]"#,
+                r#"[{"op":"replace","path":"/1","value":"nine"}]"#,
+            ),
+            (
+                r#"{"a": [1]}"#,
+                "[1]",
+                r#"[{"op":"replace","path":"","value":[1]}]"#,
+            ),
+            // nhiều op nhỏ dài hơn 1 op replace cả value
+            (
+                "[1, 2, 3]",
+                "[4, 5, 6]",
+                r#"[{"op":"replace","path":"","value":[4,5,6]}]"#,
+            ),
+            (
+                r#"{"a": {"x": 1, "y": 2}, "b": "kept as it is, so not worth replacing"}"#,
+                r#"{"a": {"z": 3}, "b": "kept as it is, so not worth replacing"}"#,
+                r#"[{"op":"replace","path":"/a","value":{"z":3}}]"#,
+            ),
+        ];
+        for (from, to, expected) in cases {
+            let (from, to) = (parse(from).unwrap(), parse(to).unwrap());
+            let patch = diff(&from, &to);
+            assert_eq!(patch.to_string(), expected);
+
+            let mut doc = from.clone();
+            patch.apply(&mut doc).unwrap();
+            assert_eq!(doc, to);
+            assert_eq!(Patch::from_value(&patch.to_value()), Ok(patch));
+        }
+    }
+
+    #[test]
+    fn test_align() {
+        // mọi cặp array ngắn gồm 0, 1, 2: kết quả phải đúng và có ít Remove/Insert nhất
+        let mut arrays: Vec<Vec<Value>> = vec![vec![]];
+        for len in 1..=4 {
+            for code in 0..3usize.pow(len) {
+                let digits = (0..len).map(|k| Value::from((code / 3usize.pow(k) % 3) as i64));
+                arrays.push(digits.collect());
+            }
+        }
+        for a in &arrays {
+            for b in &arrays {
+                let steps = align(a, b);
+                let (mut i, mut j, mut same) = (0, 0, 0);
+                for step in steps {
+                    match step {
+                        Step::Same(x, y) => {
+                            assert_eq!((x, y), (i, j));
+                            assert_eq!(a[x], b[y]);
+                            (i, j, same) = (i + 1, j + 1, same + 1);
+                        }
+                        Step::Change(x, y) => {
+                            assert_eq!((x, y), (i, j));
+                            (i, j) = (i + 1, j + 1);
+                        }
+                        Step::Remove(x) => (i, _) = (x + 1, assert_eq!(x, i)),
+                        Step::Insert(y) => (j, _) = (y + 1, assert_eq!(y, j)),
+                    }
+                }
+                assert_eq!((i, j), (a.len(), b.len()));
+                assert_eq!(same, lcs_len(a, b), "{:?} -> {:?}", a, b);
+            }
+        }
+    }
+
+    fn lcs_len(a: &[Value], b: &[Value]) -> usize {
+        let mut lcs = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for i in 0..a.len() {
+            for j in 0..b.len() {
+                lcs[i + 1][j + 1] = if a[i] == b[j] {
+                    lcs[i][j] + 1
+                } else {
+                    lcs[i][j + 1].max(lcs[i + 1][j])
+                };
+            }
+        }
+        lcs[a.len()][b.len()]
+    }
+
+    #[test]
+    fn test_diff_large_arrays() {
+        // không được cấp phát bảng n * m
+        let from = Value::Array((0..50_000).map(Value::from).collect());
+        let to = Value::Array((0..50_000).map(|n| Value::from(n + 50_000)).collect());
+        assert_eq!(
+            diff(&from, &to).0,
+            vec![Operation::Replace {
+                path: String::new(),
+                value: to.clone(),
+            }]
+        );
+
+        let mut items: Vec<Value> = (0..50_000).map(Value::from).collect();
+        items.remove(100);
+        items.insert(40_000, Value::from("new"));
+        let to = Value::Array(items);
+        let patch = diff(&from, &to);
+        assert_eq!(patch.0.len(), 2);
+        let mut doc = from.clone();
+        patch.apply(&mut doc).unwrap();
+        assert_eq!(doc, to);
+    }
+
+    #[test]
+    fn test_diff_fixtures() {
+        let fixtures: Vec<Value> = (1..=5)
+            .map(|i| {
+                let src = std::fs::read_to_string(format!("tests/{}.json", i))
+                    .expect("Can not found test file");
+                parse(&src).unwrap()
+            })
+            .collect();
+        for from in &fixtures {
+            for to in &fixtures {
+                let mut doc = from.clone();
+                diff(from, to).apply(&mut doc).unwrap();
+                assert_eq!(&doc, to);
+            }
+        }
+    }
+}
@@ -0,0 +1,800 @@
+// JSONPath (RFC 9535, một phần): `$`, `.name`, `[n]`, `[*]`, `..`, slice và filter,
+// trả về reference tới các value khớp trong document
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+use crate::{read_number, Lexer, Number, Value};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathError {
+    message: String,
+    offset: usize,
+}
+
+impl PathError {
+    fn new(message: impl Into<String>, offset: usize) -> Self {
+        PathError {
+            message: message.into(),
+            offset,
+        }
+    }
+
+    // byte offset trong chuỗi JSONPath
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid JSONPath at byte {}: {}",
+            self.offset, self.message
+        )
+    }
+}
+
+impl Error for PathError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    // `.name`, `[...]`
+    Child(Vec<Selector>),
+    // `..name`, `..[...]`: chính node đó và mọi node con cháu
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    Filter(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    // `@.isbn`: đúng khi query có ít nhất 1 kết quả
+    Exists(Query),
+    Compare(Operand, CompareOp, Operand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Literal(Value),
+    // chỉ query trả về tối đa 1 node (singular query)
+    Query(Query),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Query {
+    // `$` thay vì `@`
+    absolute: bool,
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> Result<JsonPath, PathError> {
+        let mut parser = Parser {
+            input: path,
+            pos: 0,
+        };
+        parser.expect('$')?;
+        let segments = parser.segments()?;
+        if parser.pos < path.len() {
+            return Err(parser.error("unexpected character"));
+        }
+        Ok(JsonPath { segments })
+    }
+
+    pub fn query<'v>(&self, root: &'v Value) -> Vec<&'v Value> {
+        select(vec![root], &self.segments, root)
+    }
+}
+
+impl Value {
+    // `value.query("$.store.book[?@.price < 10].title")`
+    pub fn query(&self, path: &str) -> Result<Vec<&Value>, PathError> {
+        Ok(JsonPath::parse(path)?.query(self))
+    }
+}
+
+fn select<'v>(mut nodes: Vec<&'v Value>, segments: &[Segment], root: &'v Value) -> Vec<&'v Value> {
+    for segment in segments {
+        let mut out = vec![];
+        for node in nodes {
+            match segment {
+                Segment::Child(selectors) => apply(selectors, node, root, &mut out),
+                Segment::Descendant(selectors) => {
+                    let mut stack = vec![node];
+                    // pre-order, giữ thứ tự của document
+                    while let Some(current) = stack.pop() {
+                        apply(selectors, current, root, &mut out);
+                        let start = stack.len();
+                        stack.extend(children(current));
+                        stack[start..].reverse();
+                    }
+                }
+            }
+        }
+        nodes = out;
+    }
+    nodes
+}
+
+fn children(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(object) => object.values().collect(),
+        _ => vec![],
+    }
+}
+
+fn apply<'v>(selectors: &[Selector], node: &'v Value, root: &'v Value, out: &mut Vec<&'v Value>) {
+    for selector in selectors {
+        match (selector, node) {
+            (Selector::Name(name), Value::Object(object)) => out.extend(object.get(name.as_str())),
+            (Selector::Wildcard, _) => out.extend(children(node)),
+            (Selector::Index(i), Value::Array(items)) => {
+                let i = if *i < 0 { *i + items.len() as i64 } else { *i };
+                if i >= 0 {
+                    out.extend(items.get(i as usize));
+                }
+            }
+            (Selector::Slice { start, end, step }, Value::Array(items)) => out.extend(
+                slice(items.len() as i64, *start, *end, *step)
+                    .into_iter()
+                    .map(|i| &items[i]),
+            ),
+            (Selector::Filter(expr), _) => {
+                out.extend(children(node).into_iter().filter(|c| eval(expr, c, root)))
+            }
+            _ => {}
+        }
+    }
+}
+
+// RFC 9535, section 2.3.4.2.2
+fn slice(len: i64, start: Option<i64>, end: Option<i64>, step: Option<i64>) -> Vec<usize> {
+    let step = step.unwrap_or(1);
+    let normalize = |i: i64| if i >= 0 { i } else { len + i };
+    let mut res = vec![];
+
+    if step > 0 {
+        let lower = normalize(start.unwrap_or(0)).clamp(0, len);
+        let upper = normalize(end.unwrap_or(len)).clamp(0, len);
+        let mut i = lower;
+        while i < upper {
+            res.push(i as usize);
+            // step rất lớn có thể tràn i64, khi đó đã vượt qua `upper`
+            match i.checked_add(step) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+    } else if step < 0 {
+        let upper = normalize(start.unwrap_or(len - 1)).clamp(-1, len - 1);
+        let lower = normalize(end.unwrap_or(-len - 1)).clamp(-1, len - 1);
+        let mut i = upper;
+        while lower < i {
+            res.push(i as usize);
+            match i.checked_add(step) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+    }
+    res
+}
+
+fn eval(expr: &Expr, current: &Value, root: &Value) -> bool {
+    match expr {
+        Expr::Or(a, b) => eval(a, current, root) || eval(b, current, root),
+        Expr::And(a, b) => eval(a, current, root) && eval(b, current, root),
+        Expr::Not(e) => !eval(e, current, root),
+        Expr::Exists(query) => !run_query(query, current, root).is_empty(),
+        Expr::Compare(a, op, b) => {
+            let a = operand(a, current, root);
+            let b = operand(b, current, root);
+            match op {
+                CompareOp::Eq => equal(a, b),
+                CompareOp::Ne => !equal(a, b),
+                CompareOp::Lt => less(a, b),
+                CompareOp::Le => less(a, b) || equal(a, b),
+                CompareOp::Gt => less(b, a),
+                CompareOp::Ge => less(b, a) || equal(a, b),
+            }
+        }
+    }
+}
+
+fn run_query<'v>(query: &Query, current: &'v Value, root: &'v Value) -> Vec<&'v Value> {
+    let start = if query.absolute { root } else { current };
+    select(vec![start], &query.segments, root)
+}
+
+// None là "Nothing": query không có kết quả
+fn operand<'v>(operand: &'v Operand, current: &'v Value, root: &'v Value) -> Option<&'v Value> {
+    match operand {
+        Operand::Literal(value) => Some(value),
+        Operand::Query(query) => run_query(query, current, root).first().copied(),
+    }
+}
+
+fn equal(a: Option<&Value>, b: Option<&Value>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => values_equal(a, b),
+        _ => false,
+    }
+}
+
+// như `==` nhưng 1 và 1.0 bằng nhau
+pub(crate) fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b) == Some(Ordering::Equal),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values_equal(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, v)| b.get(k).is_some_and(|other| values_equal(v, other)))
+        }
+        _ => a == b,
+    }
+}
+
+// chỉ so sánh được number với number, string với string
+fn less(a: Option<&Value>, b: Option<&Value>) -> bool {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => {
+            compare_numbers(a, b) == Some(Ordering::Less)
+        }
+        (Some(Value::String(a)), Some(Value::String(b))) => a < b,
+        _ => false,
+    }
+}
+
+pub(crate) fn compare_numbers(a: &Number, b: &Number) -> Option<Ordering> {
+    let exact = |n: &Number| match *n {
+        Number::Int(n) => Some(n as i128),
+        Number::UInt(n) => Some(n as i128),
+        Number::Float(_) => None,
+    };
+    let float = |n: &Number| match *n {
+        Number::Int(n) => n as f64,
+        Number::UInt(n) => n as f64,
+        Number::Float(n) => n,
+    };
+    match (exact(a), exact(b)) {
+        (Some(a), Some(b)) => Some(a.cmp(&b)),
+        _ => float(a).partial_cmp(&float(b)),
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> PathError {
+        PathError::new(message, self.pos)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        if self.input[self.pos..].starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), PathError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", c)))
+        }
+    }
+
+    fn skip_blank(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn segments(&mut self) -> Result<Vec<Segment>, PathError> {
+        let mut segments = vec![];
+        loop {
+            // whitespace is allowed between segments, but only if one follows
+            let before = self.pos;
+            self.skip_blank();
+            match self.peek() {
+                Some('.') | Some('[') => segments.push(self.segment()?),
+                _ => {
+                    self.pos = before;
+                    return Ok(segments);
+                }
+            }
+        }
+    }
+
+    fn segment(&mut self) -> Result<Segment, PathError> {
+        if self.eat_str("..") {
+            if self.peek() == Some('[') {
+                return Ok(Segment::Descendant(self.bracket()?));
+            }
+            return Ok(Segment::Descendant(vec![self.shorthand()?]));
+        }
+        if self.eat('.') {
+            return Ok(Segment::Child(vec![self.shorthand()?]));
+        }
+        Ok(Segment::Child(self.bracket()?))
+    }
+
+    // `*` hoặc member name sau `.` / `..`
+    fn shorthand(&mut self) -> Result<Selector, PathError> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            let first = self.pos == start;
+            if c.is_ascii_alphabetic()
+                || c == '_'
+                || !c.is_ascii()
+                || (!first && c.is_ascii_digit())
+            {
+                self.pos += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+        if self.pos == start {
+            return Err(self.error("expected a member name or `*`"));
+        }
+        Ok(Selector::Name(self.input[start..self.pos].to_string()))
+    }
+
+    // `[sel, sel, ...]`
+    fn bracket(&mut self) -> Result<Vec<Selector>, PathError> {
+        self.expect('[')?;
+        let mut selectors = vec![];
+        loop {
+            self.skip_blank();
+            selectors.push(self.selector()?);
+            self.skip_blank();
+            if self.eat(']') {
+                return Ok(selectors);
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected `,` or `]`"));
+            }
+        }
+    }
+
+    fn selector(&mut self) -> Result<Selector, PathError> {
+        match self.peek() {
+            Some('\'') | Some('"') => Ok(Selector::Name(self.string()?)),
+            Some('*') => {
+                self.pos += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some('?') => {
+                self.pos += 1;
+                self.skip_blank();
+                Ok(Selector::Filter(self.or_expr()?))
+            }
+            _ => {
+                let start = self.integer()?;
+                self.skip_blank();
+                if self.peek() != Some(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("expected a selector"));
+                }
+                self.pos += 1;
+                self.skip_blank();
+                let end = self.integer()?;
+                self.skip_blank();
+                let step = if self.eat(':') {
+                    self.skip_blank();
+                    self.integer()?
+                } else {
+                    None
+                };
+                Ok(Selector::Slice { start, end, step })
+            }
+        }
+    }
+
+    // số nguyên có thể âm, None nếu không có số nào
+    fn integer(&mut self) -> Result<Option<i64>, PathError> {
+        let start = self.pos;
+        self.eat('-');
+        let digits = self.pos;
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.pos += 1;
+        }
+        if self.pos == digits {
+            if self.pos != start {
+                return Err(self.error("expected a digit"));
+            }
+            return Ok(None);
+        }
+        let text = &self.input[start..self.pos];
+        if (text.starts_with('0') && text.len() > 1) || text.starts_with("-0") {
+            return Err(PathError::new("leading zeros are not allowed", start));
+        }
+        text.parse()
+            .map(Some)
+            .map_err(|_| PathError::new("integer out of range", start))
+    }
+
+    // '...' hoặc "..." với escape như JSON
+    fn string(&mut self) -> Result<String, PathError> {
+        let quote = self.peek().unwrap_or('"');
+        self.pos += 1;
+        let mut res = String::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += c.len_utf8();
+            match c {
+                c if c == quote => return Ok(res),
+                '\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += escaped.len_utf8();
+                    res.push(match escaped {
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape()?,
+                        '/' | '\\' | '\'' | '"' => escaped,
+                        _ => return Err(self.error("invalid escape sequence")),
+                    });
+                }
+                c if c < '\u{20}' => return Err(self.error("control character in string")),
+                c => res.push(c),
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, PathError> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.eat_str("\\u") {
+                return Err(self.error("lone surrogate in string"));
+            }
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("lone surrogate in string"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, PathError> {
+        let hex = self.input.get(self.pos..self.pos + 4).unwrap_or("");
+        let code = u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    // a || b
+    fn or_expr(&mut self) -> Result<Expr, PathError> {
+        let mut expr = self.and_expr()?;
+        loop {
+            self.skip_blank();
+            if !self.eat_str("||") {
+                return Ok(expr);
+            }
+            self.skip_blank();
+            expr = Expr::Or(Box::new(expr), Box::new(self.and_expr()?));
+        }
+    }
+
+    // a && b
+    fn and_expr(&mut self) -> Result<Expr, PathError> {
+        let mut expr = self.basic_expr()?;
+        loop {
+            self.skip_blank();
+            if !self.eat_str("&&") {
+                return Ok(expr);
+            }
+            self.skip_blank();
+            expr = Expr::And(Box::new(expr), Box::new(self.basic_expr()?));
+        }
+    }
+
+    // `!expr`, `(expr)`, `query`, `a <op> b`
+    fn basic_expr(&mut self) -> Result<Expr, PathError> {
+        if self.eat('!') {
+            self.skip_blank();
+            return Ok(Expr::Not(Box::new(self.basic_expr()?)));
+        }
+        if self.eat('(') {
+            self.skip_blank();
+            let expr = self.or_expr()?;
+            self.skip_blank();
+            self.expect(')')?;
+            return Ok(expr);
+        }
+
+        let start = self.pos;
+        let left = self.operand()?;
+        let before = self.pos;
+        self.skip_blank();
+        let Some(op) = self.compare_op() else {
+            self.pos = before;
+            return match left {
+                Operand::Query(query) => Ok(Expr::Exists(query)),
+                Operand::Literal(_) => Err(PathError::new("expected a comparison", start)),
+            };
+        };
+        self.skip_blank();
+        let right = self.operand()?;
+
+        for side in [&left, &right] {
+            if let Operand::Query(query) = side {
+                if !is_singular(query) {
+                    return Err(PathError::new(
+                        "only singular queries can be compared",
+                        start,
+                    ));
+                }
+            }
+        }
+        Ok(Expr::Compare(left, op, right))
+    }
+
+    fn compare_op(&mut self) -> Option<CompareOp> {
+        let ops = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            ("<=", CompareOp::Le),
+            (">=", CompareOp::Ge),
+            ("<", CompareOp::Lt),
+            (">", CompareOp::Gt),
+        ];
+        ops.into_iter()
+            .find(|(s, _)| self.eat_str(s))
+            .map(|(_, op)| op)
+    }
+
+    fn operand(&mut self) -> Result<Operand, PathError> {
+        match self.peek() {
+            Some('@') | Some('$') => {
+                let absolute = self.peek() == Some('$');
+                self.pos += 1;
+                let segments = self.segments()?;
+                Ok(Operand::Query(Query { absolute, segments }))
+            }
+            Some('\'') | Some('"') => Ok(Operand::Literal(Value::String(self.string()?))),
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                // số theo cú pháp JSON, dùng lại parser của crate
+                let mut src = Lexer::new(&self.input[self.pos..]);
+                let number = read_number(&mut src).map_err(|_| self.error("invalid number"))?;
+                self.pos += src.position().offset;
+                Ok(Operand::Literal(Value::Number(number)))
+            }
+            _ if self.eat_str("true") => Ok(Operand::Literal(Value::True)),
+            _ if self.eat_str("false") => Ok(Operand::Literal(Value::False)),
+            _ if self.eat_str("null") => Ok(Operand::Literal(Value::Null)),
+            _ => Err(self.error("expected a query or a literal")),
+        }
+    }
+}
+
+// chỉ gồm các segment `.name` / `[index]` có đúng 1 selector
+fn is_singular(query: &Query) -> bool {
+    query.segments.iter().all(|segment| {
+        matches!(segment, Segment::Child(selectors)
+            if matches!(selectors.as_slice(), [Selector::Name(_)] | [Selector::Index(_)]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    // ví dụ trong RFC 9535, section 1.5
+    fn bookstore() -> Value {
+        parse(
+            r#"{ "store": {
+                "book": [
+                  { "category": "reference", "author": "Nigel Rees",
+                    "title": "Sayings of the Century", "price": 8.95 },
+                  { "category": "fiction", "author": "Evelyn Waugh",
+                    "title": "Sword of Honour", "price": 12.99 },
+                  { "category": "fiction", "author": "Herman Melville",
+                    "title": "Moby Dick", "isbn": "0-553-21311-3", "price": 8.99 },
+                  { "category": "fiction", "author": "J. R. R. Tolkien",
+                    "title": "The Lord of the Rings", "isbn": "0-395-19395-8", "price": 22.99 }
+                ],
+                "bicycle": { "color": "red", "price": 399 }
+            } }"#,
+        )
+        .unwrap()
+    }
+
+    fn texts(value: &Value, path: &str) -> Vec<String> {
+        value
+            .query(path)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_rfc_examples() {
+        let doc = bookstore();
+        let authors = [
+            r#""Nigel Rees""#,
+            r#""Evelyn Waugh""#,
+            r#""Herman Melville""#,
+            r#""J. R. R. Tolkien""#,
+        ];
+
+        assert_eq!(texts(&doc, "$.store.book[*].author"), authors);
+        assert_eq!(texts(&doc, "$..author"), authors);
+        assert_eq!(doc.query("$.store.*").unwrap().len(), 2);
+        assert_eq!(
+            texts(&doc, "$.store..price"),
+            ["8.95", "12.99", "8.99", "22.99", "399"]
+        );
+        assert_eq!(texts(&doc, "$..book[2].title"), [r#""Moby Dick""#]);
+        assert_eq!(
+            texts(&doc, "$..book[-1].title"),
+            [r#""The Lord of the Rings""#]
+        );
+        assert_eq!(texts(&doc, "$..book[0,1].price"), ["8.95", "12.99"]);
+        assert_eq!(texts(&doc, "$..book[:2].price"), ["8.95", "12.99"]);
+        assert_eq!(
+            texts(&doc, "$..book[?@.isbn].title"),
+            [r#""Moby Dick""#, r#""The Lord of the Rings""#]
+        );
+        assert_eq!(
+            texts(&doc, "$..book[?@.price<10].title"),
+            [r#""Sayings of the Century""#, r#""Moby Dick""#]
+        );
+        assert_eq!(doc.query("$..*").unwrap().len(), 27);
+    }
+
+    #[test]
+    fn test_filters() {
+        let doc = parse(
+            r#"{"people": [
+                {"name": "An", "age": 19, "tags": ["a"]},
+                {"name": "Binh", "age": 27.0},
+                {"name": "Chi", "age": 35, "vip": true},
+                {"name": "Dung"}
+            ], "limit": 27}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            texts(&doc, "$.people[?@.age > 20].name"),
+            [r#""Binh""#, r#""Chi""#]
+        );
+        assert_eq!(texts(&doc, "$.people[?@.age == 27].name"), [r#""Binh""#]);
+        assert_eq!(
+            texts(&doc, "$.people[?@.age == $.limit].name"),
+            [r#""Binh""#]
+        );
+        assert_eq!(
+            texts(&doc, "$.people[?@.age >= 27 && !@.vip].name"),
+            [r#""Binh""#]
+        );
+        assert_eq!(
+            texts(&doc, "$.people[?(@.age < 20 || @.vip == true)].name"),
+            [r#""An""#, r#""Chi""#]
+        );
+        assert_eq!(texts(&doc, "$.people[?@.age != 19].name").len(), 3);
+        assert_eq!(texts(&doc, "$.people[?@.tags[0] == 'a'].name"), [r#""An""#]);
+        assert_eq!(texts(&doc, "$.people[?@.name < \"B\"].age"), ["19"]);
+    }
+
+    #[test]
+    fn test_slices() {
+        let doc = parse("[0, 1, 2, 3, 4, 5, 6]").unwrap();
+        assert_eq!(texts(&doc, "$[1:3]"), ["1", "2"]);
+        assert_eq!(texts(&doc, "$[5:]"), ["5", "6"]);
+        assert_eq!(texts(&doc, "$[1:5:2]"), ["1", "3"]);
+        assert_eq!(texts(&doc, "$[5:1:-2]"), ["5", "3"]);
+        assert_eq!(texts(&doc, "$[::-1]").len(), 7);
+        assert_eq!(texts(&doc, "$[-2:]"), ["5", "6"]);
+        assert!(texts(&doc, "$[::0]").is_empty());
+        assert!(texts(&doc, "$[9]").is_empty());
+
+        // step cực lớn không được làm tràn số
+        let small = parse("[1, 2, 3]").unwrap();
+        assert_eq!(texts(&small, "$[1::9223372036854775807]"), ["2"]);
+        assert_eq!(texts(&small, "$[::-9223372036854775808]"), ["3"]);
+        assert_eq!(texts(&small, "$[-1::-9223372036854775808]"), ["3"]);
+    }
+
+    #[test]
+    fn test_fixture_5() {
+        let src = std::fs::read_to_string("tests/5.json").expect("Can not found test file");
+        let doc = parse(&src).unwrap();
+        assert_eq!(texts(&doc, "$.presenter.name"), [r#""Dung""#]);
+        assert_eq!(texts(&doc, "$['organizers'][1]"), [r#""techfest""#]);
+        assert_eq!(texts(&doc, "$..age"), ["27"]);
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        let doc = Value::Null;
+        for path in [
+            "store",
+            "$.",
+            "$[",
+            "$[1",
+            "$[01]",
+            "$['a'",
+            "$[?@.a ==]",
+            "$[?1]",
+            "$[?@..a == 1]",
+            "$ ",
+            "$.a b",
+        ] {
+            assert!(doc.query(path).is_err(), "{}", path);
+        }
+        assert_eq!(JsonPath::parse("$.a[x]").unwrap_err().offset(), 4);
+    }
+}
@@ -0,0 +1,312 @@
+// JSON Pointer (RFC 6901): "/presenter/name" trỏ tới 1 value bên trong document
+use std::error::Error;
+use std::fmt;
+
+use crate::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointerError {
+    // pointer không rỗng phải bắt đầu bằng `/`, `~` chỉ đi với `0` hoặc `1`
+    InvalidSyntax(String),
+    // không có value tại pointer này
+    NotFound(String),
+    // value tại pointer này không phải object hay array
+    NotAContainer(String),
+    // token không phải index hợp lệ của array
+    InvalidIndex(String),
+    // không thể xoá chính document
+    RemoveRoot,
+}
+
+impl fmt::Display for PointerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PointerError::InvalidSyntax(p) => write!(f, "invalid JSON pointer `{}`", p),
+            PointerError::NotFound(p) => write!(f, "no value at `{}`", p),
+            PointerError::NotAContainer(p) => {
+                write!(f, "value at `{}` is not an object or array", p)
+            }
+            PointerError::InvalidIndex(p) => write!(f, "invalid array index at `{}`", p),
+            PointerError::RemoveRoot => f.write_str("can not remove the whole document"),
+        }
+    }
+}
+
+impl Error for PointerError {}
+
+impl Value {
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        let tokens = parse_pointer(pointer).ok()?;
+        tokens
+            .iter()
+            .try_fold(self, |value, token| child(value, token))
+    }
+
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        let tokens = parse_pointer(pointer).ok()?;
+        tokens
+            .iter()
+            .try_fold(self, |value, token| child_mut(value, token))
+    }
+
+    // đặt `value` tại `pointer`, trả về value cũ nếu có.
+    // object: thêm hoặc thay key; array: thay phần tử, hoặc thêm vào cuối
+    // với index == len hay `-`. Các container ở giữa phải có sẵn.
+    pub fn set_pointer(
+        &mut self,
+        pointer: &str,
+        value: Value,
+    ) -> Result<Option<Value>, PointerError> {
+        let mut tokens = parse_pointer(pointer)?;
+        let Some(last) = tokens.pop() else {
+            return Ok(Some(std::mem::replace(self, value)));
+        };
+
+        match walk_mut(self, &tokens)? {
+            Value::Object(object) => Ok(object.insert(last, value)),
+            Value::Array(items) => match array_index(&last) {
+                Some(i) if i < items.len() => Ok(Some(std::mem::replace(&mut items[i], value))),
+                Some(i) if i == items.len() => {
+                    items.push(value);
+                    Ok(None)
+                }
+                None if last == "-" => {
+                    items.push(value);
+                    Ok(None)
+                }
+                _ => Err(PointerError::InvalidIndex(pointer.to_string())),
+            },
+            _ => Err(PointerError::NotAContainer(to_pointer(&tokens))),
+        }
+    }
+
+    // xoá và trả về value tại `pointer`, các phần tử sau trong array dồn lên
+    pub fn remove_pointer(&mut self, pointer: &str) -> Result<Value, PointerError> {
+        let mut tokens = parse_pointer(pointer)?;
+        let Some(last) = tokens.pop() else {
+            return Err(PointerError::RemoveRoot);
+        };
+
+        match walk_mut(self, &tokens)? {
+            Value::Object(object) => object
+                .remove(&last)
+                .ok_or_else(|| PointerError::NotFound(pointer.to_string())),
+            Value::Array(items) => match array_index(&last) {
+                Some(i) if i < items.len() => Ok(items.remove(i)),
+                Some(_) => Err(PointerError::NotFound(pointer.to_string())),
+                None => Err(PointerError::InvalidIndex(pointer.to_string())),
+            },
+            _ => Err(PointerError::NotAContainer(to_pointer(&tokens))),
+        }
+    }
+}
+
+// "/a~1b/0" -> ["a/b", "0"]
+pub(crate) fn parse_pointer(pointer: &str) -> Result<Vec<String>, PointerError> {
+    if pointer.is_empty() {
+        return Ok(vec![]);
+    }
+    let invalid = || PointerError::InvalidSyntax(pointer.to_string());
+    let rest = pointer.strip_prefix('/').ok_or_else(invalid)?;
+    rest.split('/')
+        .map(|token| unescape(token).ok_or_else(invalid))
+        .collect()
+}
+
+// ~1 -> `/`, ~0 -> `~`
+fn unescape(token: &str) -> Option<String> {
+    let mut res = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            match chars.next() {
+                Some('0') => res.push('~'),
+                Some('1') => res.push('/'),
+                _ => return None,
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    Some(res)
+}
+
+// `~` -> ~0, `/` -> ~1
+pub(crate) fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+pub(crate) fn to_pointer(tokens: &[String]) -> String {
+    tokens.iter().map(|t| format!("/{}", escape(t))).collect()
+}
+
+// "0" hoặc số không có số 0 ở đầu
+pub(crate) fn array_index(token: &str) -> Option<usize> {
+    let valid = token == "0"
+        || (!token.starts_with('0')
+            && !token.is_empty()
+            && token.bytes().all(|b| b.is_ascii_digit()));
+    if valid {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn child<'v>(value: &'v Value, token: &str) -> Option<&'v Value> {
+    match value {
+        Value::Object(object) => object.get(token),
+        Value::Array(items) => items.get(array_index(token)?),
+        _ => None,
+    }
+}
+
+fn child_mut<'v>(value: &'v mut Value, token: &str) -> Option<&'v mut Value> {
+    match value {
+        Value::Object(object) => object.get_mut(token),
+        Value::Array(items) => items.get_mut(array_index(token)?),
+        _ => None,
+    }
+}
+
+// đi theo `tokens`, báo lỗi ở container đầu tiên bị thiếu hoặc sai kiểu
+pub(crate) fn walk_mut<'v>(
+    mut value: &'v mut Value,
+    tokens: &[String],
+) -> Result<&'v mut Value, PointerError> {
+    for (i, token) in tokens.iter().enumerate() {
+        let path = || to_pointer(&tokens[..=i]);
+        value = match value {
+            Value::Object(object) => object
+                .get_mut(token)
+                .ok_or_else(|| PointerError::NotFound(path()))?,
+            Value::Array(items) => {
+                let index = array_index(token).ok_or_else(|| PointerError::InvalidIndex(path()))?;
+                items
+                    .get_mut(index)
+                    .ok_or_else(|| PointerError::NotFound(path()))?
+            }
+            _ => return Err(PointerError::NotAContainer(to_pointer(&tokens[..i]))),
+        };
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, Number};
+
+    fn int(n: i64) -> Value {
+        Value::Number(Number::Int(n))
+    }
+
+    // ví dụ trong RFC 6901, section 5
+    #[test]
+    fn test_rfc_examples() {
+        let doc = parse(
+            r#"{"foo": ["bar", "baz"], "": 0, "a/b": 1, "c%d": 2, "e^f": 3, "g|h": 4,
+                "i\\j": 5, "k\"l": 6, " ": 7, "m~n": 8}"#,
+        )
+        .unwrap();
+
+        assert_eq!(doc.pointer(""), Some(&doc));
+        assert_eq!(
+            doc.pointer("/foo"),
+            Some(&Value::Array(vec![
+                Value::String("bar".to_string()),
+                Value::String("baz".to_string()),
+            ]))
+        );
+        assert_eq!(
+            doc.pointer("/foo/0"),
+            Some(&Value::String("bar".to_string()))
+        );
+        assert_eq!(doc.pointer("/"), Some(&int(0)));
+        assert_eq!(doc.pointer("/a~1b"), Some(&int(1)));
+        assert_eq!(doc.pointer("/c%d"), Some(&int(2)));
+        assert_eq!(doc.pointer("/e^f"), Some(&int(3)));
+        assert_eq!(doc.pointer("/g|h"), Some(&int(4)));
+        assert_eq!(doc.pointer("/i\\j"), Some(&int(5)));
+        assert_eq!(doc.pointer("/k\"l"), Some(&int(6)));
+        assert_eq!(doc.pointer("/ "), Some(&int(7)));
+        assert_eq!(doc.pointer("/m~0n"), Some(&int(8)));
+
+        assert_eq!(doc.pointer("/foo/01"), None);
+        assert_eq!(doc.pointer("/foo/2"), None);
+        assert_eq!(doc.pointer("/foo/-"), None);
+        assert_eq!(doc.pointer("foo"), None);
+        assert_eq!(doc.pointer("/m~2n"), None);
+    }
+
+    #[test]
+    fn test_pointer_mut() {
+        let mut doc = parse(r#"{"presenter": {"name": "Dung"}}"#).unwrap();
+        *doc.pointer_mut("/presenter/name").unwrap() = Value::String("Dũng".to_string());
+        assert_eq!(
+            doc.pointer("/presenter/name"),
+            Some(&Value::String("Dũng".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_set_pointer() {
+        let mut doc = parse(r#"{"a": {"b": [1, 2]}, "s": "x"}"#).unwrap();
+
+        assert_eq!(doc.set_pointer("/a/c", int(3)), Ok(None));
+        assert_eq!(doc.set_pointer("/a/b/0", int(10)), Ok(Some(int(1))));
+        assert_eq!(doc.set_pointer("/a/b/2", int(30)), Ok(None));
+        assert_eq!(doc.set_pointer("/a/b/-", int(40)), Ok(None));
+        assert_eq!(
+            crate::to_string(&doc),
+            r#"{"a":{"b":[10,2,30,40],"c":3},"s":"x"}"#
+        );
+
+        assert_eq!(
+            doc.set_pointer("/x/y", int(1)),
+            Err(PointerError::NotFound("/x".to_string()))
+        );
+        assert_eq!(
+            doc.set_pointer("/s/y", int(1)),
+            Err(PointerError::NotAContainer("/s".to_string()))
+        );
+        assert_eq!(
+            doc.set_pointer("/a/b/9", int(1)),
+            Err(PointerError::InvalidIndex("/a/b/9".to_string()))
+        );
+        assert_eq!(
+            doc.set_pointer("/a/b/x/y", int(1)),
+            Err(PointerError::InvalidIndex("/a/b/x".to_string()))
+        );
+        assert_eq!(
+            doc.set_pointer("a", int(1)),
+            Err(PointerError::InvalidSyntax("a".to_string()))
+        );
+
+        assert!(doc.set_pointer("", Value::Null).unwrap().is_some());
+        assert_eq!(doc, Value::Null);
+    }
+
+    #[test]
+    fn test_remove_pointer() {
+        let mut doc = parse(r#"{"a": [1, 2, 3], "m~n": true}"#).unwrap();
+
+        assert_eq!(doc.remove_pointer("/a/0"), Ok(int(1)));
+        assert_eq!(doc.remove_pointer("/m~0n"), Ok(Value::True));
+        assert_eq!(crate::to_string(&doc), r#"{"a":[2,3]}"#);
+
+        assert_eq!(
+            doc.remove_pointer("/a/5"),
+            Err(PointerError::NotFound("/a/5".to_string()))
+        );
+        assert_eq!(
+            doc.remove_pointer("/b"),
+            Err(PointerError::NotFound("/b".to_string()))
+        );
+        assert_eq!(
+            doc.remove_pointer("/a/0/x"),
+            Err(PointerError::NotAContainer("/a/0".to_string()))
+        );
+        assert_eq!(doc.remove_pointer(""), Err(PointerError::RemoveRoot));
+    }
+}
@@ -0,0 +1,173 @@
+// Value -> JSON text có xuống dòng và thụt lề, dùng để format lại file config
+use std::fmt::Write;
+
+use crate::ser::write_string;
+use crate::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    Spaces(usize),
+    Tab,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrettyConfig {
+    pub indent: Indent,
+    // `"key": value` thay vì `"key":value`
+    pub space_after_colon: bool,
+    // sort keys, otherwise they are written in insertion order
+    pub sort_keys: bool,
+    // arrays of scalars whose one-line form fits in this many chars
+    // stay on one line, 0 turns this off
+    pub inline_array_width: usize,
+}
+
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        PrettyConfig {
+            indent: Indent::Spaces(2),
+            space_after_colon: true,
+            sort_keys: false,
+            inline_array_width: 0,
+        }
+    }
+}
+
+pub fn to_string_pretty(value: &Value, config: &PrettyConfig) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, config, 0);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value, config: &PrettyConfig, depth: usize) {
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            if let Some(line) = inline_array(items, config) {
+                out.push_str(&line);
+                return;
+            }
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_newline(out, config, depth + 1);
+                write_value(out, item, config, depth + 1);
+            }
+            write_newline(out, config, depth);
+            out.push(']');
+        }
+        Value::Object(object) if !object.is_empty() => {
+            let mut entries: Vec<_> = object.iter().collect();
+            if config.sort_keys {
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+            }
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_newline(out, config, depth + 1);
+                // writing into a String can not fail
+                let _ = write_string(out, key);
+                out.push(':');
+                if config.space_after_colon {
+                    out.push(' ');
+                }
+                write_value(out, value, config, depth + 1);
+            }
+            write_newline(out, config, depth);
+            out.push('}');
+        }
+        // scalars and empty containers look the same as in compact output
+        _ => {
+            let _ = write!(out, "{}", value);
+        }
+    }
+}
+
+// `[1, 2, 3]` if every item is a scalar and the line is short enough
+fn inline_array(items: &[Value], config: &PrettyConfig) -> Option<String> {
+    let nested = items.iter().any(|item| match item {
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(object) => !object.is_empty(),
+        _ => false,
+    });
+    if nested {
+        return None;
+    }
+
+    let parts: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+    let line = format!("[{}]", parts.join(", "));
+    (line.chars().count() <= config.inline_array_width).then_some(line)
+}
+
+fn write_newline(out: &mut String, config: &PrettyConfig, depth: usize) {
+    out.push('\n');
+    for _ in 0..depth {
+        match config.indent {
+            Indent::Spaces(width) => out.extend(std::iter::repeat_n(' ', width)),
+            Indent::Tab => out.push('\t'),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn fixture() -> Value {
+        let src = std::fs::read_to_string("tests/5.json").expect("Can not found test file");
+        parse(&src).unwrap()
+    }
+
+    #[test]
+    fn test_sorted_keys() {
+        let config = PrettyConfig {
+            sort_keys: true,
+            ..PrettyConfig::default()
+        };
+        let expectation = r#"{
+  "live": true,
+  "organizers": [
+    "vbi",
+    "techfest"
+  ],
+  "presenter": {
+    "age": 27,
+    "name": "Dung",
+    "occupation": "Engineer"
+  },
+  "title": "Rust",
+  "year": 2023
+}"#;
+        assert_eq!(to_string_pretty(&fixture(), &config), expectation);
+    }
+
+    #[test]
+    fn test_tabs_and_inline_arrays() {
+        let config = PrettyConfig {
+            indent: Indent::Tab,
+            space_after_colon: false,
+            sort_keys: true,
+            inline_array_width: 20,
+        };
+        let value =
+            parse(r#"{"short":[1,[],"a"],"long":["aaaaaaaaaa","bbbbbbbbbb"],"nested":[[1]]}"#)
+                .unwrap();
+        let expectation = "{\n\t\"long\":[\n\t\t\"aaaaaaaaaa\",\n\t\t\"bbbbbbbbbb\"\n\t],\n\t\"nested\":[\n\t\t[1]\n\t],\n\t\"short\":[1, [], \"a\"]\n}";
+        assert_eq!(to_string_pretty(&value, &config), expectation);
+    }
+
+    #[test]
+    fn test_pretty_round_trip() {
+        let value = fixture();
+        let text = to_string_pretty(&value, &PrettyConfig::default());
+        assert_eq!(parse(&text), Ok(value));
+        assert_eq!(
+            to_string_pretty(&Value::Object(Default::default()), &PrettyConfig::default()),
+            "{}"
+        );
+    }
+}
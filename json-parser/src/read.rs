@@ -0,0 +1,116 @@
+// nguồn char cho Lexer: 1 &str có sẵn trong bộ nhớ, hoặc 1 io::Read
+// được đọc dần qua buffer để không phải load cả file vào bộ nhớ
+use std::io::{self, Read};
+use std::str::Chars;
+
+use crate::error::ErrorKind;
+
+const BUFFER_SIZE: usize = 8 * 1024;
+
+pub(crate) enum Input<'a> {
+    // cả input và iterator đang đọc nó, để có thể mượn lại 1 đoạn của input
+    Str(&'a str, Chars<'a>),
+    Reader(IoReader<'a>),
+}
+
+impl<'a> Input<'a> {
+    pub(crate) fn next_char(&mut self) -> Option<char> {
+        match self {
+            Input::Str(_, chars) => chars.next(),
+            Input::Reader(reader) => reader.next_char(),
+        }
+    }
+
+    // input[start..end] khi input là &str, không copy
+    pub(crate) fn slice(&self, start: usize, end: usize) -> Option<&'a str> {
+        match self {
+            Input::Str(input, _) => input.get(start..end),
+            Input::Reader(_) => None,
+        }
+    }
+
+    // lỗi đọc/decode làm input dừng sớm, nếu có
+    pub(crate) fn error(&self) -> Option<ErrorKind> {
+        match self {
+            Input::Str(..) => None,
+            Input::Reader(reader) => reader.error,
+        }
+    }
+}
+
+pub(crate) struct IoReader<'a> {
+    reader: Box<dyn Read + 'a>,
+    buf: Box<[u8]>,
+    // bytes chưa decode nằm trong buf[start..end]
+    start: usize,
+    end: usize,
+    eof: bool,
+    error: Option<ErrorKind>,
+}
+
+impl<'a> IoReader<'a> {
+    pub(crate) fn new(reader: impl Read + 'a) -> Self {
+        IoReader {
+            reader: Box::new(reader),
+            buf: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            start: 0,
+            end: 0,
+            eof: false,
+            error: None,
+        }
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        if self.error.is_some() || !self.fill(1) {
+            return None;
+        }
+
+        let width = match self.buf[self.start] {
+            0x00..=0x7F => 1,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return self.fail(ErrorKind::InvalidUtf8),
+        };
+        if !self.fill(width) {
+            // the stream ended in the middle of a character
+            return self.fail(self.error.unwrap_or(ErrorKind::InvalidUtf8));
+        }
+
+        // from_utf8 also rejects overlong forms and surrogates
+        let bytes = &self.buf[self.start..self.start + width];
+        match std::str::from_utf8(bytes) {
+            Ok(s) => {
+                self.start += width;
+                s.chars().next()
+            }
+            Err(_) => self.fail(ErrorKind::InvalidUtf8),
+        }
+    }
+
+    // make sure at least `n` bytes are buffered, false if the stream ends first
+    fn fill(&mut self, n: usize) -> bool {
+        while self.end - self.start < n {
+            if self.eof || self.error.is_some() {
+                return false;
+            }
+            if self.start > 0 {
+                self.buf.copy_within(self.start..self.end, 0);
+                self.end -= self.start;
+                self.start = 0;
+            }
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Ok(0) => self.eof = true,
+                Ok(read) => self.end += read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => self.error = Some(ErrorKind::Io(e.kind())),
+            }
+        }
+        true
+    }
+
+    fn fail(&mut self, kind: ErrorKind) -> Option<char> {
+        self.error = Some(kind);
+        None
+    }
+}
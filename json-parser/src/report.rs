@@ -0,0 +1,235 @@
+// so sánh 2 Value, liệt kê các path được thêm / xoá / thay đổi
+use std::fmt;
+
+use crate::patch::{align, Step};
+use crate::pointer::escape;
+use crate::{to_string_pretty, PrettyConfig, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl Change {
+    // JSON Pointer; removed theo index của value cũ, còn lại theo value mới
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Changed { path, .. } => path,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report {
+    changes: Vec<Change>,
+}
+
+pub fn compare(old: &Value, new: &Value) -> Report {
+    let mut changes = vec![];
+    compare_values(old, new, String::new(), &mut changes);
+    Report { changes }
+}
+
+impl Report {
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    // dạng unified diff, mỗi path 1 hunk; `color` thêm mã màu ANSI
+    pub fn unified(&self, color: bool) -> String {
+        let paint = |code: &str, line: String| {
+            if color {
+                format!("\x1b[{}m{}\x1b[0m\n", code, line)
+            } else {
+                format!("{}\n", line)
+            }
+        };
+        let lines = |sign: char, value: &Value| -> String {
+            to_string_pretty(value, &PrettyConfig::default())
+                .lines()
+                .map(|line| {
+                    paint(
+                        if sign == '-' { "31" } else { "32" },
+                        format!("{} {}", sign, line),
+                    )
+                })
+                .collect()
+        };
+
+        let mut res = paint("1", "--- old".to_string()) + &paint("1", "+++ new".to_string());
+        for change in &self.changes {
+            let path = if change.path().is_empty() {
+                "(root)"
+            } else {
+                change.path()
+            };
+            res += &paint("36", format!("@@ {} @@", path));
+            match change {
+                Change::Added { value, .. } => res += &lines('+', value),
+                Change::Removed { value, .. } => res += &lines('-', value),
+                Change::Changed { old, new, .. } => {
+                    res += &lines('-', old);
+                    res += &lines('+', new);
+                }
+            }
+        }
+        res
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.unified(false))
+    }
+}
+
+fn compare_values(old: &Value, new: &Value, path: String, changes: &mut Vec<Change>) {
+    if old == new {
+        return;
+    }
+    let child = |token: &str| format!("{}/{}", path, escape(token));
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, value) in a.iter() {
+                match b.get(key) {
+                    Some(other) => compare_values(value, other, child(key), changes),
+                    None => changes.push(Change::Removed {
+                        path: child(key),
+                        value: value.clone(),
+                    }),
+                }
+            }
+            for (key, value) in b.iter().filter(|(key, _)| !a.contains_key(*key)) {
+                changes.push(Change::Added {
+                    path: child(key),
+                    value: value.clone(),
+                });
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for step in align(a, b) {
+                match step {
+                    Step::Same(..) => {}
+                    Step::Change(i, j) => {
+                        compare_values(&a[i], &b[j], child(&j.to_string()), changes)
+                    }
+                    Step::Remove(i) => changes.push(Change::Removed {
+                        path: child(&i.to_string()),
+                        value: a[i].clone(),
+                    }),
+                    Step::Insert(j) => changes.push(Change::Added {
+                        path: child(&j.to_string()),
+                        value: b[j].clone(),
+                    }),
+                }
+            }
+        }
+        _ => changes.push(Change::Changed {
+            path,
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+// như `assert_eq!` cho Value, nhưng khi fail chỉ in những chỗ khác nhau
+#[macro_export]
+macro_rules! assert_json_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let report = $crate::compare(left, right);
+                if !report.is_empty() {
+                    panic!("assertion `left == right` failed\n{}", report);
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_changes() {
+        let old = parse(
+            r#"{"port": 80, "log": {"level": "info", "file": "a.log"}, "hosts": ["a", "b", "c"]}"#,
+        )
+        .unwrap();
+        let new = parse(
+            r#"{"port": 8080, "log": {"level": "info"}, "hosts": ["a", "c", "d"], "tls": true}"#,
+        )
+        .unwrap();
+
+        let paths: Vec<String> = compare(&old, &new)
+            .changes()
+            .iter()
+            .map(|c| {
+                let kind = match c {
+                    Change::Added { .. } => '+',
+                    Change::Removed { .. } => '-',
+                    Change::Changed { .. } => '~',
+                };
+                format!("{}{}", kind, c.path())
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            ["~/port", "-/log/file", "-/hosts/1", "+/hosts/2", "+/tls"]
+        );
+        assert!(compare(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_unified() {
+        let old = parse(r#"{"a": 1, "b": {"c": [1]}}"#).unwrap();
+        let new = parse(r#"{"a": 2, "b": {"c": [1, {"d": null}]}}"#).unwrap();
+        let report = compare(&old, &new);
+
+        assert_eq!(
+            report.to_string(),
+            "--- old\n+++ new\n@@ /a @@\n- 1\n+ 2\n@@ /b/c/1 @@\n+ {\n+   \"d\": null\n+ }\n"
+        );
+        let colored = report.unified(true);
+        assert!(
+            colored.contains("\x1b[36m@@ /a @@\x1b[0m\n\x1b[31m- 1\x1b[0m\n\x1b[32m+ 2\x1b[0m\n")
+        );
+
+        let report = compare(&Value::Null, &Value::True);
+        assert_eq!(
+            report.to_string(),
+            "--- old\n+++ new\n@@ (root) @@\n- null\n+ true\n"
+        );
+    }
+
+    #[test]
+    fn test_assert_json_eq() {
+        assert_json_eq!(parse("[1, {}]").unwrap(), parse("[1, {}]").unwrap());
+        let res = std::panic::catch_unwind(|| {
+            assert_json_eq!(parse(r#"{"a": 1}"#).unwrap(), parse(r#"{"a": 2}"#).unwrap())
+        });
+        let message = res.unwrap_err();
+        let message = message.downcast_ref::<String>().unwrap();
+        assert!(message.ends_with("@@ /a @@\n- 1\n+ 2\n"), "{}", message);
+    }
+}
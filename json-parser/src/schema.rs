@@ -0,0 +1,656 @@
+// JSON Schema (draft 2020-12, một phần): type, properties, required, items, enum,
+// const, minimum/maximum, minLength/maxLength, pattern, additionalProperties,
+// allOf/anyOf/oneOf và `$ref` trong cùng document
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use regex_lite::Regex;
+
+use crate::path::{compare_numbers, values_equal};
+use crate::pointer::escape;
+use crate::{Number, Value};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    path: String,
+    message: String,
+}
+
+impl SchemaError {
+    // JSON Pointer tới chỗ sai trong schema
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid schema at `{}`: {}", self.path, self.message)
+    }
+}
+
+impl Error for SchemaError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    // JSON Pointer tới value bị sai trong document
+    pub instance_path: String,
+    // JSON Pointer tới keyword trong schema, đi qua cả `$ref`
+    pub schema_path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = if self.instance_path.is_empty() {
+            "(root)"
+        } else {
+            &self.instance_path
+        };
+        write!(
+            f,
+            "{}: {} (schema `{}`)",
+            path, self.message, self.schema_path
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Schema {
+    root: Value,
+    // `pattern` được compile sẵn
+    patterns: HashMap<String, Regex>,
+}
+
+impl Schema {
+    // kiểm tra `pattern` và `$ref` trước, để lỗi của schema không lẫn vào kết quả validate
+    pub fn new(schema: Value) -> Result<Schema, SchemaError> {
+        let mut patterns = HashMap::new();
+        check(&schema, &schema, "", &mut patterns, &mut HashSet::new())?;
+        Ok(Schema {
+            root: schema,
+            patterns,
+        })
+    }
+
+    // trả về mọi violation chứ không dừng ở cái đầu tiên
+    pub fn validate(&self, instance: &Value) -> Result<(), Vec<Violation>> {
+        let mut violations = vec![];
+        self.validate_at(&self.root, instance, "", "", &mut vec![], &mut violations);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    pub fn is_valid(&self, instance: &Value) -> bool {
+        self.validate(instance).is_ok()
+    }
+
+    fn validate_at(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        instance_path: &str,
+        schema_path: &str,
+        // các cặp (đích của `$ref`, instance_path) đang được validate
+        refs: &mut Vec<(String, String)>,
+        out: &mut Vec<Violation>,
+    ) {
+        let mut fail = |keyword: &str, message: String| {
+            out.push(Violation {
+                instance_path: instance_path.to_string(),
+                schema_path: format!("{}/{}", schema_path, keyword),
+                message,
+            })
+        };
+        let schema = match schema {
+            Value::True => return,
+            Value::False => {
+                out.push(Violation {
+                    instance_path: instance_path.to_string(),
+                    schema_path: schema_path.to_string(),
+                    message: "no value is allowed here".to_string(),
+                });
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => {
+                out.push(Violation {
+                    instance_path: instance_path.to_string(),
+                    schema_path: schema_path.to_string(),
+                    message: "a schema must be an object or a boolean".to_string(),
+                });
+                return;
+            }
+        };
+
+        if let Some(expected) = schema.get("type") {
+            let names: Vec<&str> = match expected {
+                Value::String(name) => vec![name],
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                _ => vec![],
+            };
+            if !names.iter().any(|name| has_type(instance, name)) {
+                fail(
+                    "type",
+                    format!(
+                        "expected {}, found {}",
+                        names.join(" or "),
+                        type_name(instance)
+                    ),
+                );
+            }
+        }
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            if !values.iter().any(|v| values_equal(v, instance)) {
+                fail(
+                    "enum",
+                    format!(
+                        "{} is not one of {}",
+                        instance,
+                        Value::Array(values.clone())
+                    ),
+                );
+            }
+        }
+        if let Some(value) = schema.get("const") {
+            if !values_equal(value, instance) {
+                fail("const", format!("expected {}, found {}", value, instance));
+            }
+        }
+
+        if let Value::Number(n) = instance {
+            if let Some(Value::Number(min)) = schema.get("minimum") {
+                if compare_numbers(n, min) == Some(Ordering::Less) {
+                    fail("minimum", format!("{} is less than {}", n, min));
+                }
+            }
+            if let Some(Value::Number(max)) = schema.get("maximum") {
+                if compare_numbers(n, max) == Some(Ordering::Greater) {
+                    fail("maximum", format!("{} is greater than {}", n, max));
+                }
+            }
+        }
+
+        if let Value::String(s) = instance {
+            let len = s.chars().count();
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if (len as u64) < min {
+                    fail("minLength", format!("length {} is less than {}", len, min));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if len as u64 > max {
+                    fail(
+                        "maxLength",
+                        format!("length {} is greater than {}", len, max),
+                    );
+                }
+            }
+            if let Some(Value::String(pattern)) = schema.get("pattern") {
+                match self.patterns.get(pattern) {
+                    Some(regex) if regex.is_match(s) => {}
+                    Some(_) => fail(
+                        "pattern",
+                        format!("{} does not match `{}`", instance, pattern),
+                    ),
+                    None => fail("pattern", format!("`{}` was not compiled", pattern)),
+                }
+            }
+        }
+
+        if let Value::Object(object) = instance {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(key) {
+                        fail("required", format!("missing property `{}`", key));
+                    }
+                }
+            }
+            let properties = match schema.get("properties") {
+                Some(Value::Object(properties)) => Some(properties),
+                _ => None,
+            };
+            for (key, value) in object.iter() {
+                let instance_path = format!("{}/{}", instance_path, escape(key));
+                if let Some(sub) = properties.and_then(|p| p.get(key)) {
+                    let schema_path = format!("{}/properties/{}", schema_path, escape(key));
+                    self.validate_at(sub, value, &instance_path, &schema_path, refs, out);
+                } else if let Some(sub) = schema.get("additionalProperties") {
+                    let schema_path = format!("{}/additionalProperties", schema_path);
+                    if *sub == Value::False {
+                        out.push(Violation {
+                            instance_path,
+                            schema_path,
+                            message: format!("additional property `{}` is not allowed", key),
+                        });
+                    } else {
+                        self.validate_at(sub, value, &instance_path, &schema_path, refs, out);
+                    }
+                }
+            }
+        }
+
+        if let (Value::Array(items), Some(sub)) = (instance, schema.get("items")) {
+            let schema_path = format!("{}/items", schema_path);
+            for (i, item) in items.iter().enumerate() {
+                let instance_path = format!("{}/{}", instance_path, i);
+                self.validate_at(sub, item, &instance_path, &schema_path, refs, out);
+            }
+        }
+
+        if let Some(Value::Array(subs)) = schema.get("allOf") {
+            for (i, sub) in subs.iter().enumerate() {
+                let schema_path = format!("{}/allOf/{}", schema_path, i);
+                self.validate_at(sub, instance, instance_path, &schema_path, refs, out);
+            }
+        }
+        if let Some(Value::Array(subs)) = schema.get("anyOf") {
+            let matched = subs
+                .iter()
+                .any(|sub| self.matches(sub, instance, instance_path, refs));
+            if !matched {
+                out.push(Violation {
+                    instance_path: instance_path.to_string(),
+                    schema_path: format!("{}/anyOf", schema_path),
+                    message: "does not match any schema in anyOf".to_string(),
+                });
+            }
+        }
+        if let Some(Value::Array(subs)) = schema.get("oneOf") {
+            let matched = subs
+                .iter()
+                .filter(|sub| self.matches(sub, instance, instance_path, refs))
+                .count();
+            if matched != 1 {
+                out.push(Violation {
+                    instance_path: instance_path.to_string(),
+                    schema_path: format!("{}/oneOf", schema_path),
+                    message: format!("matches {} schemas in oneOf, expected exactly one", matched),
+                });
+            }
+        }
+
+        if let Some(Value::String(reference)) = schema.get("$ref") {
+            let schema_path = format!("{}/$ref", schema_path);
+            // quay lại cùng 1 đích với cùng 1 value thì không bao giờ dừng
+            let pair = (
+                reference.strip_prefix('#').unwrap_or(reference).to_string(),
+                instance_path.to_string(),
+            );
+            if refs.contains(&pair) {
+                out.push(Violation {
+                    instance_path: instance_path.to_string(),
+                    schema_path,
+                    message: format!("`{}` loops back without consuming the value", reference),
+                });
+            } else if let Some(target) = resolve(&self.root, reference) {
+                refs.push(pair);
+                self.validate_at(target, instance, instance_path, &schema_path, refs, out);
+                refs.pop();
+            } else {
+                out.push(Violation {
+                    instance_path: instance_path.to_string(),
+                    schema_path,
+                    message: format!("can not resolve `{}`", reference),
+                });
+            }
+        }
+    }
+
+    fn matches(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        instance_path: &str,
+        refs: &mut Vec<(String, String)>,
+    ) -> bool {
+        let mut violations = vec![];
+        self.validate_at(schema, instance, instance_path, "", refs, &mut violations);
+        violations.is_empty()
+    }
+}
+
+// đi qua mọi subschema, compile `pattern` và kiểm tra `$ref`;
+// đích của `$ref` có thể nằm ở bất kỳ đâu nên cũng được đi qua, mỗi đích 1 lần
+fn check(
+    root: &Value,
+    schema: &Value,
+    path: &str,
+    patterns: &mut HashMap<String, Regex>,
+    visited: &mut HashSet<String>,
+) -> Result<(), SchemaError> {
+    let error = |keyword: &str, message: String| SchemaError {
+        path: format!("{}/{}", path, keyword),
+        message,
+    };
+    let schema = match schema {
+        Value::True | Value::False => return Ok(()),
+        Value::Object(schema) => schema,
+        _ => {
+            return Err(SchemaError {
+                path: path.to_string(),
+                message: "a schema must be an object or a boolean".to_string(),
+            })
+        }
+    };
+
+    if let Some(Value::String(pattern)) = schema.get("pattern") {
+        let regex = Regex::new(pattern).map_err(|e| error("pattern", e.to_string()))?;
+        patterns.insert(pattern.clone(), regex);
+    }
+    if let Some(Value::String(reference)) = schema.get("$ref") {
+        let target = resolve(root, reference)
+            .ok_or_else(|| error("$ref", format!("can not resolve `{}`", reference)))?;
+        if visited.insert(reference.clone()) {
+            // lỗi được báo theo vị trí của đích, ví dụ "/foo/pattern"
+            let target_path = reference.strip_prefix('#').unwrap_or_default();
+            check(root, target, target_path, patterns, visited)?;
+        }
+    }
+
+    for keyword in ["properties", "$defs", "definitions"] {
+        if let Some(Value::Object(subs)) = schema.get(keyword) {
+            for (key, sub) in subs.iter() {
+                check(
+                    root,
+                    sub,
+                    &format!("{}/{}/{}", path, keyword, escape(key)),
+                    patterns,
+                    visited,
+                )?;
+            }
+        }
+    }
+    for keyword in ["items", "additionalProperties"] {
+        if let Some(sub) = schema.get(keyword) {
+            check(
+                root,
+                sub,
+                &format!("{}/{}", path, keyword),
+                patterns,
+                visited,
+            )?;
+        }
+    }
+    for keyword in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(subs)) = schema.get(keyword) {
+            for (i, sub) in subs.iter().enumerate() {
+                check(
+                    root,
+                    sub,
+                    &format!("{}/{}/{}", path, keyword, i),
+                    patterns,
+                    visited,
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// chỉ hỗ trợ "#" và "#/json/pointer"
+fn resolve<'v>(root: &'v Value, reference: &str) -> Option<&'v Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match (name, value) {
+        ("null", Value::Null) => true,
+        ("boolean", Value::True | Value::False) => true,
+        ("string", Value::String(_)) => true,
+        ("array", Value::Array(_)) => true,
+        ("object", Value::Object(_)) => true,
+        ("number", Value::Number(_)) => true,
+        // 1.0 cũng là integer
+        ("integer", Value::Number(n)) => match n {
+            Number::Float(f) => f.fract() == 0.0,
+            _ => true,
+        },
+        _ => false,
+    }
+}
+
+pub(crate) fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::True | Value::False => "boolean",
+        Value::Number(_) if has_type(value, "integer") => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn compile(src: &str) -> Schema {
+        Schema::new(parse(src).unwrap()).unwrap()
+    }
+
+    // (instance path, schema path) của mọi violation
+    fn violations(schema: &Schema, instance: &str) -> Vec<(String, String)> {
+        match schema.validate(&parse(instance).unwrap()) {
+            Ok(()) => vec![],
+            Err(violations) => violations
+                .into_iter()
+                .map(|v| (v.instance_path, v.schema_path))
+                .collect(),
+        }
+    }
+
+    fn paths(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(i, s)| (i.to_string(), s.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_keywords() {
+        let schema = compile(
+            r#"{
+                "type": "object",
+                "required": ["title", "year"],
+                "properties": {
+                    "title": {"type": "string", "minLength": 1, "maxLength": 10},
+                    "year": {"type": "integer", "minimum": 2000, "maximum": 2100},
+                    "live": {"const": true},
+                    "organizers": {"type": "array", "items": {"enum": ["vbi", "techfest"]}},
+                    "presenter": {
+                        "type": "object",
+                        "properties": {"email": {"type": "string", "pattern": "^[^@]+@[^@]+$"}},
+                        "additionalProperties": {"type": ["string", "integer"]}
+                    }
+                },
+                "additionalProperties": false
+            }"#,
+        );
+
+        let src = std::fs::read_to_string("tests/5.json").expect("Can not found test file");
+        assert_eq!(violations(&schema, &src), vec![]);
+
+        assert_eq!(
+            violations(
+                &schema,
+                r#"{"title": "", "year": 1999.5, "live": false, "organizers": ["vbi", "x"],
+                    "presenter": {"email": "dung", "age": 27.5}, "extra": 1}"#,
+            ),
+            paths(&[
+                ("/title", "/properties/title/minLength"),
+                ("/year", "/properties/year/type"),
+                ("/year", "/properties/year/minimum"),
+                ("/live", "/properties/live/const"),
+                ("/organizers/1", "/properties/organizers/items/enum"),
+                (
+                    "/presenter/email",
+                    "/properties/presenter/properties/email/pattern"
+                ),
+                (
+                    "/presenter/age",
+                    "/properties/presenter/additionalProperties/type"
+                ),
+                ("/extra", "/additionalProperties"),
+            ])
+        );
+        assert_eq!(
+            violations(&schema, r#"{"title": "a very long title"}"#),
+            paths(&[("", "/required"), ("/title", "/properties/title/maxLength")])
+        );
+        assert_eq!(violations(&schema, "[]"), paths(&[("", "/type")]));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let schema = compile(
+            r#"{
+                "allOf": [{"type": "number"}, {"minimum": 0}],
+                "anyOf": [{"maximum": 10}, {"minimum": 100}],
+                "oneOf": [{"type": "integer"}, {"minimum": 5}]
+            }"#,
+        );
+        assert_eq!(violations(&schema, "3"), vec![]);
+        assert_eq!(violations(&schema, "5.5"), vec![]);
+        assert_eq!(violations(&schema, "7"), paths(&[("", "/oneOf")]));
+        assert_eq!(
+            violations(&schema, "50"),
+            paths(&[("", "/anyOf"), ("", "/oneOf")])
+        );
+        assert_eq!(
+            violations(&schema, "-50"),
+            paths(&[("", "/allOf/1/minimum")])
+        );
+        assert_eq!(
+            violations(&schema, r#""x""#),
+            paths(&[("", "/allOf/0/type")])
+        );
+    }
+
+    #[test]
+    fn test_ref() {
+        let schema = compile(
+            r##"{
+                "$defs": {
+                    "node": {
+                        "type": "object",
+                        "required": ["value"],
+                        "properties": {
+                            "value": {"type": "integer"},
+                            "children": {"type": "array", "items": {"$ref": "#/$defs/node"}}
+                        }
+                    }
+                },
+                "$ref": "#/$defs/node"
+            }"##,
+        );
+        assert_eq!(
+            violations(
+                &schema,
+                r#"{"value": 1, "children": [{"value": 2}, {"value": "x"}, {}]}"#
+            ),
+            paths(&[
+                (
+                    "/children/1/value",
+                    "/$ref/properties/children/items/$ref/properties/value/type"
+                ),
+                (
+                    "/children/2",
+                    "/$ref/properties/children/items/$ref/required"
+                ),
+            ])
+        );
+
+        let looping = compile(r##"{"allOf": [{"$ref": "#"}]}"##);
+        assert_eq!(
+            violations(&looping, "1"),
+            paths(&[("", "/allOf/0/$ref/allOf/0/$ref")])
+        );
+    }
+
+    #[test]
+    fn test_ref_cycles() {
+        // mỗi nhánh lại quay về "#" với cùng value, không được nổ theo hàm mũ
+        for keyword in ["anyOf", "allOf", "oneOf"] {
+            let schema = compile(&format!(
+                r##"{{"{}": [{{"$ref": "#"}}, {{"$ref": "#"}}]}}"##,
+                keyword
+            ));
+            assert!(!schema.is_valid(&parse("1").unwrap()));
+        }
+
+        // đi xuống value con thì `$ref` về "#" vẫn hợp lệ
+        let tree =
+            compile(r##"{"type": "array", "items": {"anyOf": [{"$ref": "#"}, {"$ref": "#"}]}}"##);
+        assert!(tree.is_valid(&parse("[[], [[]]]").unwrap()));
+        assert!(!tree.is_valid(&parse("[[], [1]]").unwrap()));
+    }
+
+    #[test]
+    fn test_boolean_schemas() {
+        assert_eq!(violations(&compile("true"), "1"), vec![]);
+        assert_eq!(violations(&compile("false"), "1"), paths(&[("", "")]));
+        let items = compile(r#"{"items": false}"#);
+        assert_eq!(violations(&items, "[]"), vec![]);
+        assert_eq!(violations(&items, "[1]"), paths(&[("/0", "/items")]));
+    }
+
+    #[test]
+    fn test_messages() {
+        let schema =
+            compile(r#"{"properties": {"age": {"type": "integer"}}, "required": ["name"]}"#);
+        let messages: Vec<String> = schema
+            .validate(&parse(r#"{"age": "27"}"#).unwrap())
+            .unwrap_err()
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "(root): missing property `name` (schema `/required`)",
+                "/age: expected integer, found string (schema `/properties/age/type`)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        let err =
+            Schema::new(parse(r#"{"properties": {"a": {"pattern": "("}}}"#).unwrap()).unwrap_err();
+        assert_eq!(err.path(), "/properties/a/pattern");
+        let err =
+            Schema::new(parse(r##"{"items": {"$ref": "#/$defs/missing"}}"##).unwrap()).unwrap_err();
+        assert_eq!(err.path(), "/items/$ref");
+        let err = Schema::new(parse(r#"{"allOf": [1]}"#).unwrap()).unwrap_err();
+        assert_eq!(err.path(), "/allOf/0");
+    }
+
+    #[test]
+    fn test_ref_outside_keywords() {
+        // `$ref` trỏ tới chỗ không phải $defs / properties / ...
+        let schema = compile(r##"{"$ref": "#/foo", "foo": {"pattern": "a"}}"##);
+        assert!(!schema.is_valid(&parse(r#""x""#).unwrap()));
+        assert!(schema.is_valid(&parse(r#""bar""#).unwrap()));
+        assert_eq!(
+            violations(&schema, r#""x""#),
+            paths(&[("", "/$ref/pattern")])
+        );
+
+        let err = Schema::new(parse(r##"{"$ref": "#/foo", "foo": {"pattern": "("}}"##).unwrap())
+            .unwrap_err();
+        assert_eq!(err.path(), "/foo/pattern");
+        // đích không resolve được hoặc không phải schema
+        let err = Schema::new(parse(r##"{"$ref": "#/missing"}"##).unwrap()).unwrap_err();
+        assert_eq!(err.path(), "/$ref");
+        let err = Schema::new(parse(r##"{"$ref": "#/foo", "foo": 1}"##).unwrap()).unwrap_err();
+        assert_eq!(err.path(), "/foo");
+    }
+}
@@ -0,0 +1,188 @@
+// Value -> JSON text (compact, không có whitespace thừa)
+use std::fmt::{self, Display, Write};
+
+use crate::{Number, Value};
+
+pub fn to_string(value: &Value) -> String {
+    value.to_string()
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::True => f.write_str("true"),
+            Value::False => f.write_str("false"),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write_string(f, s),
+            Value::Array(items) => {
+                f.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_char(']')
+            }
+            Value::Object(object) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in object.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    f.write_char(':')?;
+                    write!(f, "{}", value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Number::Int(n) => write!(f, "{}", n),
+            Number::UInt(n) => write!(f, "{}", n),
+            // JSON has no NaN or Infinity
+            Number::Float(n) if !n.is_finite() => f.write_str("null"),
+            // `{:?}` always keeps a `.` or an exponent (1.0, 1e300),
+            // so the number is read back as a float
+            Number::Float(n) => write!(f, "{:?}", n),
+        }
+    }
+}
+
+// escape `"`, `\` and the control characters, everything else is written as is
+pub(crate) fn write_string(f: &mut impl Write, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\u{8}' => f.write_str("\\b")?,
+            '\u{c}' => f.write_str("\\f")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c < '\u{20}' => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, Map};
+
+    #[test]
+    fn test_compact_output() {
+        let mut body = Map::new();
+        body.insert(
+            "list".to_string(),
+            Value::Array(vec![
+                Value::Null,
+                Value::True,
+                Value::Number(Number::Int(-3)),
+                Value::Number(Number::Float(1.0)),
+                Value::Number(Number::Float(2.5e-8)),
+            ]),
+        );
+        assert_eq!(
+            to_string(&Value::Object(body)),
+            r#"{"list":[null,true,-3,1.0,2.5e-8]}"#
+        );
+        assert_eq!(
+            to_string(&Value::String("a\"b\\c\n\u{1}/é".to_string())),
+            r#""a\"b\\c\n\u0001/é""#
+        );
+    }
+
+    #[test]
+    fn test_non_finite() {
+        // `infinity_nan` đọc được, nhưng ghi ra là null
+        let options = crate::ParseOptions {
+            infinity_nan: true,
+            ..Default::default()
+        };
+        let value = crate::parse_with("[Infinity, -Infinity, NaN, 1.5]", &options).unwrap();
+        assert!(value[2].as_f64().unwrap().is_nan());
+        assert_eq!(to_string(&value), "[null,null,null,1.5]");
+        assert_eq!(parse(&to_string(&value)), parse("[null, null, null, 1.5]"));
+    }
+
+    #[test]
+    fn test_round_trip_fixtures() {
+        for i in 1..=5 {
+            let src = std::fs::read_to_string(format!("tests/{}.json", i))
+                .expect("Can not found test file");
+            let value = parse(&src).unwrap();
+            assert_eq!(parse(&to_string(&value)), Ok(value));
+        }
+    }
+
+    // xorshift, đủ để sinh dữ liệu test mà không cần thêm crate
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    fn gen_string(rng: &mut Rng) -> String {
+        const CHARS: &[char] = &[
+            'a', 'Z', '0', ' ', '"', '\\', '/', '\n', '\t', '\u{0}', '\u{1f}', 'é', '中', '🦀',
+        ];
+        (0..rng.below(8))
+            .map(|_| CHARS[rng.below(CHARS.len() as u64) as usize])
+            .collect()
+    }
+
+    fn gen_value(rng: &mut Rng, depth: u32) -> Value {
+        let kinds = if depth == 0 { 7 } else { 9 };
+        match rng.below(kinds) {
+            0 => Value::Null,
+            1 => Value::True,
+            2 => Value::False,
+            3 => Value::Number(Number::Int(rng.next() as i64)),
+            4 => Value::Number(Number::UInt(rng.next() | 1 << 63)),
+            5 => {
+                let n = f64::from_bits(rng.next());
+                Value::Number(Number::Float(if n.is_finite() { n } else { 0.5 }))
+            }
+            6 => Value::String(gen_string(rng)),
+            7 => Value::Array(
+                (0..rng.below(4))
+                    .map(|_| gen_value(rng, depth - 1))
+                    .collect(),
+            ),
+            _ => Value::Object(
+                (0..rng.below(4))
+                    .map(|_| (gen_string(rng), gen_value(rng, depth - 1)))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_generated() {
+        let mut rng = Rng(0x2023_0bad_cafe_f00d);
+        for _ in 0..500 {
+            let value = gen_value(&mut rng, 4);
+            let text = to_string(&value);
+            assert_eq!(parse(&text), Ok(value), "{}", text);
+        }
+    }
+}
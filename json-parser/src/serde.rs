@@ -0,0 +1,1068 @@
+// serde (feature `serde`): Serialize/Deserialize cho Value, Value làm Serializer /
+// Deserializer, và from_str đọc thẳng từ Events, không dựng Value ở giữa
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::de::value::{
+    BorrowedStrDeserializer, MapDeserializer, SeqDeserializer, StringDeserializer,
+};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::ser::{self, Serialize};
+use serde::{forward_to_deserialize_any, Deserialize};
+
+use crate::{ErrorKind, Event, Events, Map, Number, ParseError, ParseOptions, Position, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    Parse(ParseError),
+    // lỗi từ serde (sai kiểu, thiếu field...), kèm vị trí khi đọc từ chuỗi
+    Custom {
+        message: String,
+        position: Option<Position>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse(err) => err.fmt(f),
+            Error::Custom {
+                message,
+                position: Some(position),
+            } => write!(f, "{} at {}", message, position),
+            Error::Custom { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Custom {
+            message: msg.to_string(),
+            position: None,
+        }
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Custom {
+            message: msg.to_string(),
+            position: None,
+        }
+    }
+}
+
+fn custom(message: &str) -> Error {
+    <Error as de::Error>::custom(message)
+}
+
+pub fn from_str<'de, T: Deserialize<'de>>(input: &'de str) -> Result<T, Error> {
+    from_str_with(input, &ParseOptions::default())
+}
+
+pub fn from_str_with<'de, T: Deserialize<'de>>(
+    input: &'de str,
+    options: &ParseOptions,
+) -> Result<T, Error> {
+    let mut de = Deserializer::from_events(Events::with_options(input, options.clone()));
+    let value = T::deserialize(&mut de).map_err(|err| de.locate(err))?;
+    de.end()?;
+    Ok(value)
+}
+
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    Ok(crate::to_string(&to_value(value)?))
+}
+
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, Error> {
+    value.serialize(Serializer)
+}
+
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, Error> {
+    T::deserialize(value)
+}
+
+impl Serialize for Value {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => serializer.serialize_unit(),
+            Value::True => serializer.serialize_bool(true),
+            Value::False => serializer.serialize_bool(false),
+            Value::Number(Number::Int(n)) => serializer.serialize_i64(*n),
+            Value::Number(Number::UInt(n)) => serializer.serialize_u64(*n),
+            Value::Number(Number::Float(n)) => serializer.serialize_f64(*n),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::Array(items) => serializer.collect_seq(items),
+            Value::Object(object) => serializer.collect_map(object),
+        }
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut object = Map::new();
+        while let Some((key, value)) = map.next_entry()? {
+            object.insert(key, value);
+        }
+        Ok(Value::Object(object))
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+// Value -> Value: dùng cho to_value, cùng cách biểu diễn với serde_json
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = ValueSeq;
+    type SerializeTuple = ValueSeq;
+    type SerializeTupleStruct = ValueSeq;
+    type SerializeTupleVariant = ValueSeq;
+    type SerializeMap = ValueMap;
+    type SerializeStruct = ValueMap;
+    type SerializeStructVariant = ValueMap;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value, Error> {
+        match (i64::try_from(v), u64::try_from(v)) {
+            (Ok(n), _) => Ok(Value::from(n)),
+            (_, Ok(n)) => Ok(Value::from(n)),
+            _ => Err(ser::Error::custom("number out of range")),
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value, Error> {
+        u64::try_from(v)
+            .map(Value::from)
+            .map_err(|_| ser::Error::custom("number out of range"))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(Value::from(v))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::from(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    // {"Variant": value}
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        let mut object = Map::new();
+        object.insert(variant.to_string(), to_value(value)?);
+        Ok(Value::Object(object))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ValueSeq, Error> {
+        Ok(ValueSeq {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ValueSeq, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<ValueSeq, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<ValueSeq, Error> {
+        Ok(ValueSeq {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<ValueMap, Error> {
+        Ok(ValueMap {
+            variant: None,
+            object: Map::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<ValueMap, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<ValueMap, Error> {
+        Ok(ValueMap {
+            variant: Some(variant),
+            object: Map::new(),
+            key: None,
+        })
+    }
+}
+
+// {"Variant": content} nếu là variant của enum
+fn wrap(variant: Option<&'static str>, content: Value) -> Value {
+    match variant {
+        Some(variant) => {
+            let mut object = Map::new();
+            object.insert(variant.to_string(), content);
+            Value::Object(object)
+        }
+        None => content,
+    }
+}
+
+pub struct ValueSeq {
+    variant: Option<&'static str>,
+    items: Vec<Value>,
+}
+
+impl ser::SerializeSeq for ValueSeq {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.items.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(wrap(self.variant, Value::Array(self.items)))
+    }
+}
+
+impl ser::SerializeTuple for ValueSeq {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for ValueSeq {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for ValueSeq {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+pub struct ValueMap {
+    variant: Option<&'static str>,
+    object: Map<String, Value>,
+    // key đang chờ value
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for ValueMap {
+    type Ok = Value;
+    type Error = Error;
+
+    // như serde_json: key là string, số hoặc bool thì đổi sang string
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.key = Some(match to_value(key)? {
+            Value::String(s) => s,
+            key @ (Value::Number(_) | Value::True | Value::False) => key.to_string(),
+            _ => return Err(ser::Error::custom("map key must be a string")),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.key.take().ok_or_else(|| {
+            <Error as ser::Error>::custom("serialize_value called before serialize_key")
+        })?;
+        self.object.insert(key, to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(wrap(self.variant, Value::Object(self.object)))
+    }
+}
+
+impl ser::SerializeStruct for ValueMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.object.insert(key.to_string(), to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for ValueMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Value {
+        self
+    }
+}
+
+// đọc T từ 1 Value có sẵn
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::True => visitor.visit_bool(true),
+            Value::False => visitor.visit_bool(false),
+            Value::Number(Number::Int(n)) => visitor.visit_i64(n),
+            Value::Number(Number::UInt(n)) => visitor.visit_u64(n),
+            Value::Number(Number::Float(n)) => visitor.visit_f64(n),
+            Value::String(s) => visitor.visit_string(s),
+            Value::Array(items) => {
+                let mut seq = SeqDeserializer::new(items.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Object(object) => {
+                let mut map = MapDeserializer::new(object.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // "Variant" hoặc {"Variant": content}
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self {
+            Value::String(variant) => visitor.visit_enum(StringDeserializer::new(variant)),
+            Value::Object(object) if object.len() == 1 => {
+                let (variant, content) = object.into_iter().next().expect("one entry");
+                visitor.visit_enum(ValueEnum { variant, content })
+            }
+            _ => Err(custom("expected a string or an object with a single key")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct ValueEnum {
+    variant: String,
+    content: Value,
+}
+
+impl<'de> EnumAccess<'de> for ValueEnum {
+    type Error = Error;
+    type Variant = Value;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Value), Error> {
+        let variant = seed.deserialize(StringDeserializer::<Error>::new(self.variant))?;
+        Ok((variant, self.content))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Value {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self {
+            Value::Null => Ok(()),
+            _ => Err(custom("expected null for a unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(self, visitor)
+    }
+}
+
+// đọc T trực tiếp từ Events; string không có escape được mượn từ input
+pub struct Deserializer<'de> {
+    events: Events<'de>,
+    peeked: Option<Event<'de>>,
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn new(input: &'de str) -> Self {
+        Self::from_events(Events::new(input))
+    }
+
+    pub fn from_events(events: Events<'de>) -> Self {
+        Deserializer {
+            events,
+            peeked: None,
+        }
+    }
+
+    // báo lỗi nếu còn gì đó sau value đầu tiên
+    pub fn end(&mut self) -> Result<(), Error> {
+        match self.events.next() {
+            None => Ok(()),
+            Some(Err(err)) => Err(Error::Parse(err)),
+            Some(Ok(_)) => Err(Error::Parse(ParseError::new(
+                ErrorKind::TrailingCharacters,
+                self.events.event_position(),
+            ))),
+        }
+    }
+
+    fn next(&mut self) -> Result<Event<'de>, Error> {
+        if let Some(event) = self.peeked.take() {
+            return Ok(event);
+        }
+        match self.events.next() {
+            Some(res) => Ok(res?),
+            None => Err(Error::Parse(
+                ParseError::new(ErrorKind::UnexpectedEof, self.events.event_position())
+                    .with_expected("a value"),
+            )),
+        }
+    }
+
+    fn peek(&mut self) -> Result<&Event<'de>, Error> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.next()?);
+        }
+        Ok(self.peeked.as_ref().expect("just peeked"))
+    }
+
+    // lỗi của serde chưa có vị trí thì lấy vị trí event vừa đọc
+    fn locate(&self, err: Error) -> Error {
+        match err {
+            Error::Custom {
+                message,
+                position: None,
+            } => Error::Custom {
+                message,
+                position: Some(self.events.event_position()),
+            },
+            err => err,
+        }
+    }
+
+    fn expect_end(&mut self, end: Event<'static>) -> Result<(), Error> {
+        if self.next()? == end {
+            Ok(())
+        } else {
+            Err(custom("too many elements"))
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.next()? {
+            Event::Null => visitor.visit_unit(),
+            Event::Bool(b) => visitor.visit_bool(b),
+            Event::Number(Number::Int(n)) => visitor.visit_i64(n),
+            Event::Number(Number::UInt(n)) => visitor.visit_u64(n),
+            Event::Number(Number::Float(n)) => visitor.visit_f64(n),
+            Event::String(Cow::Borrowed(s)) => visitor.visit_borrowed_str(s),
+            Event::String(Cow::Owned(s)) => visitor.visit_string(s),
+            Event::StartArray => {
+                let value = visitor.visit_seq(Seq { de: &mut *self })?;
+                self.expect_end(Event::EndArray)?;
+                Ok(value)
+            }
+            Event::StartObject => {
+                let value = visitor.visit_map(Object { de: &mut *self })?;
+                self.expect_end(Event::EndObject)?;
+                Ok(value)
+            }
+            Event::Key(_) | Event::EndArray | Event::EndObject => {
+                Err(custom("unexpected event, expected a value"))
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if *self.peek()? == Event::Null {
+            self.next()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.next()? {
+            Event::String(Cow::Borrowed(s)) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
+            Event::String(Cow::Owned(s)) => visitor.visit_enum(StringDeserializer::new(s)),
+            Event::StartObject => {
+                let value = visitor.visit_enum(Enum { de: &mut *self })?;
+                match self.next()? {
+                    Event::EndObject => Ok(value),
+                    _ => Err(custom("expected an object with a single key")),
+                }
+            }
+            _ => Err(custom("expected a string or an object with a single key")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+struct Seq<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> SeqAccess<'de> for Seq<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if *self.de.peek()? == Event::EndArray {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+struct Object<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> MapAccess<'de> for Object<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.de.peek()? {
+            Event::EndObject => Ok(None),
+            Event::Key(_) => match self.de.next()? {
+                Event::Key(key) => seed.deserialize(Key(key)).map(Some),
+                _ => unreachable!(),
+            },
+            _ => Err(custom("expected a key")),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct Enum<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> EnumAccess<'de> for Enum<'_, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        match self.de.next()? {
+            Event::Key(key) => Ok((seed.deserialize(Key(key))?, self)),
+            _ => Err(custom("expected an object with a single key")),
+        }
+    }
+}
+
+impl<'de> VariantAccess<'de> for Enum<'_, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.de.next()? {
+            Event::Null => Ok(()),
+            _ => Err(custom("expected null for a unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(&mut *self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_any(&mut *self.de, visitor)
+    }
+}
+
+// key của object; HashMap<u32, _> cần đọc key thành số
+struct Key<'de>(Cow<'de, str>);
+
+macro_rules! parse_key {
+    ($($method:ident => $visit:ident,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.0.parse() {
+                Ok(n) => visitor.$visit(n),
+                Err(_) => self.deserialize_any(visitor),
+            }
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for Key<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+            Cow::Owned(s) => visitor.visit_string(s),
+        }
+    }
+
+    parse_key! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Cow::Borrowed(s) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
+            Cow::Owned(s) => visitor.visit_enum(StringDeserializer::new(s)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Presenter {
+        name: String,
+        age: u8,
+        occupation: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        title: String,
+        year: u16,
+        live: bool,
+        organizers: Vec<String>,
+        presenter: Presenter,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle { radius: f64 },
+        Square(f64),
+        Line((i32, i32), (i32, i32)),
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Borrowed<'a> {
+        name: &'a str,
+        #[serde(borrow)]
+        tags: Vec<&'a str>,
+    }
+
+    fn fixture(i: usize) -> String {
+        std::fs::read_to_string(format!("tests/{}.json", i)).expect("Can not found test file")
+    }
+
+    #[test]
+    fn test_struct_round_trip() {
+        let src = fixture(5);
+        let event: Event = from_str(&src).unwrap();
+        assert_eq!(event.presenter.occupation.as_deref(), Some("Engineer"));
+        assert_eq!(event, serde_json::from_str::<Event>(&src).unwrap());
+
+        let text = to_string(&event).unwrap();
+        assert_eq!(text, serde_json::to_string(&event).unwrap());
+        assert_eq!(from_str::<Event>(&text), Ok(event));
+    }
+
+    #[test]
+    fn test_enums() {
+        let shapes = vec![
+            Shape::Empty,
+            Shape::Circle { radius: 1.5 },
+            Shape::Square(2.0),
+            Shape::Line((0, 0), (1, -1)),
+        ];
+        let text = to_string(&shapes).unwrap();
+        assert_eq!(
+            text,
+            r#"["Empty",{"Circle":{"radius":1.5}},{"Square":2.0},{"Line":[[0,0],[1,-1]]}]"#
+        );
+        assert_eq!(from_str::<Vec<Shape>>(&text).as_ref(), Ok(&shapes));
+        assert_eq!(
+            from_value::<Vec<Shape>>(crate::parse(&text).unwrap()),
+            Ok(shapes)
+        );
+        assert_eq!(from_str::<Shape>(r#"{"Empty": null}"#), Ok(Shape::Empty));
+        assert!(from_str::<Shape>(r#"{"Square": 1, "Empty": null}"#).is_err());
+    }
+
+    #[test]
+    fn test_borrowed_strings() {
+        let src = r#"{"name": "Dung", "tags": ["a", "b"]}"#;
+        let borrowed: Borrowed = from_str(src).unwrap();
+        assert_eq!(
+            borrowed,
+            Borrowed {
+                name: "Dung",
+                tags: vec!["a", "b"]
+            }
+        );
+        // có escape thì không mượn được
+        assert!(from_str::<Borrowed>(r#"{"name": "D\u0075ng", "tags": []}"#).is_err());
+        assert_eq!(from_str::<String>(r#""Dung""#), Ok("Dung".to_string()));
+    }
+
+    #[test]
+    fn test_maps_and_options() {
+        let map: HashMap<u32, Option<bool>> = from_str(r#"{"1": true, "2": null}"#).unwrap();
+        assert_eq!(map[&1], Some(true));
+        assert_eq!(map[&2], None);
+
+        let sorted: BTreeMap<u32, Option<bool>> = map.into_iter().collect();
+        assert_eq!(to_string(&sorted).unwrap(), r#"{"1":true,"2":null}"#);
+        assert_eq!(
+            from_str::<(i8, char, ())>(r#"[-1, "x", null]"#),
+            Ok((-1, 'x', ()))
+        );
+    }
+
+    #[test]
+    fn test_value() {
+        for i in 1..=5 {
+            let src = fixture(i);
+            let value = crate::parse(&src).unwrap();
+            assert_eq!(from_str::<Value>(&src).as_ref(), Ok(&value));
+            assert_eq!(to_value(&value).as_ref(), Ok(&value));
+            assert_eq!(from_value::<Value>(value.clone()).as_ref(), Ok(&value));
+            assert_eq!(to_string(&value).unwrap(), crate::to_string(&value));
+
+            // cùng kết quả với serde_json
+            let expected: serde_json::Value = serde_json::from_str(&src).unwrap();
+            assert_eq!(from_str::<serde_json::Value>(&src).unwrap(), expected);
+            assert_eq!(serde_json::to_value(&value).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_f32() {
+        for n in [0.1f32, 1.1, -0.3, 16777216.0] {
+            assert_eq!(to_string(&n).unwrap(), serde_json::to_string(&n).unwrap());
+        }
+        for n in [0.1f32, -2.5e-8, f32::MAX, f32::MIN_POSITIVE] {
+            assert_eq!(from_str::<f32>(&to_string(&n).unwrap()), Ok(n));
+        }
+        assert_eq!(to_string(&0.1f32).unwrap(), "0.1");
+        assert_eq!(to_string(&f32::NAN).unwrap(), "null");
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = from_str::<Event>(r#"{"title": "Rust", "year": "2023"}"#).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"invalid type: string "2023", expected u16 at line 1, column 27 (byte 26)"#
+        );
+        assert!(matches!(
+            from_str::<Event>(r#"{"title": }"#),
+            Err(Error::Parse(_))
+        ));
+        assert!(matches!(from_str::<u8>("1 2"), Err(Error::Parse(_))));
+        assert!(from_str::<(u8,)>("[1, 2]").is_err());
+        assert!(from_str::<u8>("256").is_err());
+
+        let mut object = std::collections::HashMap::new();
+        object.insert(vec![1], 1);
+        assert!(to_value(&object).is_err());
+    }
+}